@@ -0,0 +1,56 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+/// Grandfathered findings for `--range`: full commit sha -> rule ids that may keep failing.
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Baseline {
+    commits: BTreeMap<String, BTreeSet<String>>,
+}
+
+impl Baseline {
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("failed to read baseline {}", path.display()))?;
+        serde_json::from_str(&content)
+            .with_context(|| format!("invalid baseline {}", path.display()))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let mut content =
+            serde_json::to_string_pretty(self).context("failed to serialize baseline")?;
+        content.push('\n');
+        fs::write(path, content)
+            .with_context(|| format!("failed to write baseline {}", path.display()))
+    }
+
+    pub fn grandfathered(&self, sha: &str) -> Option<&BTreeSet<String>> {
+        self.commits.get(sha)
+    }
+
+    pub fn record(&mut self, sha: &str, rule_ids: &[&str]) {
+        if !rule_ids.is_empty() {
+            self.commits.insert(
+                sha.to_string(),
+                rule_ids.iter().map(|id| id.to_string()).collect(),
+            );
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.commits.len()
+    }
+}
+
+/// How `--range` uses a baseline file.
+pub enum BaselineMode {
+    Off,
+    /// `--baseline`: ignore the grandfathered rule ids of each listed commit.
+    Apply(Baseline),
+    /// `--write-baseline`: record every commit's failing rule ids to this path.
+    Write(PathBuf),
+}
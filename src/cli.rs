@@ -3,6 +3,7 @@ use std::path::PathBuf;
 use clap::{Args, Parser, Subcommand};
 
 use crate::hooks::HookKind;
+use gitfluff::lint::{LengthUnit, LineEnding, SubjectCaseScope};
 
 #[derive(Debug, Clone, Copy, clap::ValueEnum)]
 pub enum ColorMode {
@@ -11,6 +12,35 @@ pub enum ColorMode {
     Never,
 }
 
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum OutputFormat {
+    #[default]
+    Human,
+    /// Human output grouped by severity, with aligned rule ids.
+    Grouped,
+    Github,
+    Json,
+    Sarif,
+    /// Selected by `--porcelain` rather than `--format`.
+    #[value(skip)]
+    Porcelain,
+}
+
+impl OutputFormat {
+    /// Formats that print one document on stdout instead of diagnostic lines.
+    pub fn is_document(self) -> bool {
+        matches!(
+            self,
+            OutputFormat::Json | OutputFormat::Sarif | OutputFormat::Porcelain
+        )
+    }
+
+    /// Formats meant for a person reading the terminal.
+    pub fn is_human(self) -> bool {
+        matches!(self, OutputFormat::Human | OutputFormat::Grouped)
+    }
+}
+
 #[derive(Debug, Parser)]
 #[command(author, version, about, propagate_version = true)]
 pub struct Cli {
@@ -20,9 +50,26 @@ pub struct Cli {
 
 #[derive(Debug, Subcommand)]
 pub enum Commands {
+    #[command(alias = "check")]
     Lint(Box<LintArgs>),
     #[command(subcommand)]
     Hook(HookSubcommand),
+    /// Describe a rule id, with a failing and a passing example.
+    Explain(ExplainArgs),
+    #[command(subcommand, hide = true)]
+    Config(ConfigCommand),
+}
+
+#[derive(Debug, Args)]
+pub struct ExplainArgs {
+    /// Rule id as shown in `error[<rule-id>]`, e.g. `type-enum`.
+    pub rule: String,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum ConfigCommand {
+    /// Print a JSON Schema for `.gitfluff.toml`.
+    Schema,
 }
 
 #[derive(Debug, Args)]
@@ -36,6 +83,53 @@ pub struct LintArgs {
     #[arg(long, conflicts_with_all = ["from_file", "stdin", "commit_file"])]
     pub message: Option<String>,
 
+    /// Lint the message of the current `HEAD` commit.
+    #[arg(
+        long,
+        alias = "head",
+        conflicts_with_all = ["from_file", "stdin", "message", "commit_file", "range", "messages_file", "write", "dry_run"]
+    )]
+    pub last: bool,
+
+    /// Lint every commit in a git revision range (e.g. `origin/main..HEAD`) instead of one message.
+    #[arg(
+        long,
+        value_name = "RANGE",
+        conflicts_with_all = ["from_file", "stdin", "message", "commit_file", "write", "dry_run"]
+    )]
+    pub range: Option<String>,
+
+    /// With --range, ignore the rule ids each commit already fails according to this baseline.
+    #[arg(long, value_name = "PATH", requires = "range")]
+    pub baseline: Option<PathBuf>,
+
+    /// With --range, record every commit's failing rule ids as a baseline and exit 0.
+    #[arg(
+        long,
+        value_name = "PATH",
+        requires = "range",
+        conflicts_with = "baseline"
+    )]
+    pub write_baseline: Option<PathBuf>,
+
+    /// Lint every message in a batch file, one record per `--delimiter`-separated chunk.
+    #[arg(
+        long,
+        alias = "message-file",
+        value_name = "PATH",
+        conflicts_with_all = ["from_file", "stdin", "message", "commit_file", "range", "write", "dry_run"]
+    )]
+    pub messages_file: Option<PathBuf>,
+
+    /// Record separator for --messages-file: `newline` (default), `nul`, or a literal string.
+    #[arg(
+        long,
+        value_name = "SEP",
+        default_value = "newline",
+        requires = "messages_file"
+    )]
+    pub delimiter: String,
+
     /// Path to the commit message file (positional for commit-msg hooks).
     #[arg(
         conflicts_with_all = ["from_file", "stdin", "message"],
@@ -44,6 +138,15 @@ pub struct LintArgs {
     )]
     pub commit_file: Option<PathBuf>,
 
+    /// Decode the message file with this encoding label (e.g. `latin1`, `utf-16le`); a byte-order
+    /// mark is detected automatically.
+    #[arg(long, value_name = "LABEL")]
+    pub encoding: Option<String>,
+
+    /// Drop a byte-order mark when `--write` rewrites the message (kept by default).
+    #[arg(long)]
+    pub strip_bom: bool,
+
     #[arg(long)]
     pub preset: Option<String>,
 
@@ -122,22 +225,151 @@ pub struct LintArgs {
     )]
     pub title_suffix_separator: String,
 
+    /// Require the Conventional Commit subject to start with a lower-case letter.
+    #[arg(long)]
+    pub subject_lowercase_first: bool,
+
+    /// Require a BREAKING CHANGE footer when the header uses a `!` breaking marker.
+    #[arg(long)]
+    pub breaking_requires_footer: bool,
+
+    /// Lint the message even while a merge commit is in progress (skipped by default).
+    #[arg(long)]
+    pub lint_merges: bool,
+
+    /// With --lint-merges, still skip octopus merges (more than two parents).
+    #[arg(long)]
+    pub skip_octopus_merges: bool,
+
+    /// Apply the subject-case check to the `full` subject (default) or its `first-word` only.
+    #[arg(long, value_name = "SCOPE")]
+    pub subject_case_scope: Option<SubjectCaseScope>,
+
+    /// Measure line-length limits in `chars` (default) or terminal display `width`.
+    #[arg(long, value_name = "UNIT")]
+    pub length_unit: Option<LengthUnit>,
+
     #[arg(long)]
     pub config: Option<PathBuf>,
 
+    /// Start config, git, and merge-state discovery here instead of the working directory.
+    #[arg(long, value_name = "PATH")]
+    pub base_dir: Option<PathBuf>,
+
+    /// Ignore every config file (discovered, `--config`, or `GITFLUFF_CONFIG`) and use only CLI
+    /// flags and built-in defaults.
+    #[arg(long, alias = "config-none", conflicts_with = "config")]
+    pub no_config: bool,
+
+    /// File listing the paths touched by the commit (one per line, `-` for stdin); config
+    /// discovery starts from their common directory instead of the working directory.
+    #[arg(long, value_name = "PATH", conflicts_with = "config")]
+    pub changed_files_from: Option<PathBuf>,
+
     #[arg(long)]
     pub write: bool,
 
-    /// Control ANSI color output (auto uses TTY detection).
+    /// Fail when the Conventional Commit subject is longer than this many characters.
+    #[arg(long, value_name = "CHARS")]
+    pub subject_max_length: Option<usize>,
+
+    /// Fail when the body has more than this many non-empty lines (footers excluded).
+    #[arg(long = "max-body-lines", value_name = "LINES")]
+    pub body_max_lines: Option<usize>,
+
+    /// Hard-wrap body paragraphs at this many columns when `--write` is used.
+    #[arg(
+        long,
+        value_name = "COLUMNS",
+        value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(1..)
+    )]
+    pub body_wrap: Option<usize>,
+
+    /// Comma-separated autofix step ids that `--write` may apply (default: all steps).
+    #[arg(long, value_name = "IDS", value_delimiter = ',')]
+    pub autofix_only: Vec<String>,
+
+    /// Line ending for rewritten messages: preserve (default), lf, or crlf.
+    #[arg(long, value_name = "MODE")]
+    pub line_ending: Option<LineEnding>,
+
+    /// Print at most this many violations (and, separately, warnings), then `... and N more`.
+    #[arg(long, value_name = "N")]
+    pub max_violations: Option<usize>,
+
+    /// Reprint the message with markers pointing at each finding.
+    #[arg(long)]
+    pub explain_failure: bool,
+
+    /// Suggest a concrete fix for each violation the autofix steps can resolve, without applying it.
+    #[arg(long)]
+    pub suggest: bool,
+
+    /// Print what each cleanup rule removed and what it inserted in its place.
+    #[arg(long)]
+    pub show_cleanup_diff: bool,
+
+    /// List every rule with whether it passed, failed, or was skipped.
+    #[arg(long)]
+    pub show_rules: bool,
+
+    /// Print every rule id with its default severity and whether `--write` fixes it, as JSON.
+    #[arg(long)]
+    pub list_rules: bool,
+
+    /// Preview the `--write` rewrite as a unified diff on stderr without touching the message.
+    #[arg(long)]
+    pub dry_run: bool,
+
+    /// Label shown on every output line (e.g. `error[commit abc123]: ...`) to tell inputs apart.
+    #[arg(long, visible_alias = "stdin-name", value_name = "LABEL")]
+    pub input_name: Option<String>,
+
+    /// With --range or --messages-file, print how many messages were linted and how long it took.
+    #[arg(long)]
+    pub timing: bool,
+
+    /// Suppress info lines (cleanup summaries, fixes); violations and warnings still print.
+    #[arg(long, short = 'q')]
+    pub quiet: bool,
+
+    /// Also print the original message, every cleanup/autofix step, and the cleaned message.
+    #[arg(long, short = 'v', conflicts_with = "quiet")]
+    pub verbose: bool,
+
+    /// Control ANSI color output (auto honors `NO_COLOR`, then `FORCE_COLOR` / `CLICOLOR_FORCE`,
+    /// then TTY detection).
     #[arg(long, value_enum, default_value = "auto")]
     pub color: ColorMode,
 
+    /// Alias for `--color never`.
+    #[arg(long, conflicts_with = "color")]
+    pub no_color: bool,
+
+    /// Output format for violations and warnings (`grouped` lists findings under `errors:` and
+    /// `warnings:` headings, `github` emits workflow annotations, `json` a single document on stdout).
+    #[arg(long, value_enum, default_value = "human")]
+    pub format: OutputFormat,
+
+    /// Stable script output on stdout: `<severity>\t<rule-id>\t<message>` per finding, then
+    /// `rewritten\t<true|false>`.
+    #[arg(long)]
+    pub porcelain: bool,
+
     #[arg(long, conflicts_with = "require_body")]
     pub single_line: bool,
 
     #[arg(long, conflicts_with = "single_line")]
     pub require_body: bool,
 
+    /// Report findings but always exit 0 (advisory mode); `--write` still rewrites the message.
+    #[arg(long)]
+    pub exit_zero: bool,
+
+    /// Sanitize only: implies `--write`, reports leftover violations as info, and always exits 0.
+    #[arg(long, conflicts_with = "dry_run")]
+    pub fix_only: bool,
+
     /// Exit with code 1 if `--write` rewrote the message (even if it becomes valid).
     #[arg(long)]
     pub exit_nonzero_on_rewrite: bool,
@@ -146,6 +378,7 @@ pub struct LintArgs {
 #[derive(Debug, Subcommand)]
 pub enum HookCommand {
     Install(HookInstallArgs),
+    Uninstall(HookUninstallArgs),
 }
 
 pub type HookSubcommand = HookCommand;
@@ -160,4 +393,30 @@ pub struct HookInstallArgs {
 
     #[arg(long)]
     pub force: bool,
+
+    /// Keep an existing hook as `<hook>.local` and run it before gitfluff.
+    #[arg(long)]
+    pub chain: bool,
+
+    /// Fall back to `$GIT_DIR/COMMIT_EDITMSG` when git passes no message file (commit-msg only).
+    #[arg(long)]
+    pub robust: bool,
+
+    /// Find the repository from here instead of the working directory.
+    #[arg(long, value_name = "PATH")]
+    pub base_dir: Option<PathBuf>,
+}
+
+#[derive(Debug, Args)]
+pub struct HookUninstallArgs {
+    #[arg(value_enum)]
+    pub kind: HookKind,
+
+    /// Remove the hook even if gitfluff did not install it.
+    #[arg(long)]
+    pub force: bool,
+
+    /// Find the repository from here instead of the working directory.
+    #[arg(long, value_name = "PATH")]
+    pub base_dir: Option<PathBuf>,
 }
@@ -0,0 +1,144 @@
+use std::fs;
+use std::path::Path;
+
+use anyhow::{Context, Result, bail};
+use serde_json::Value;
+
+use crate::config::RulesConfig;
+use crate::lint::Severity;
+
+/// Commitlint config files gitfluff knows how to read, in lookup order. JavaScript configs
+/// (`commitlint.config.js`, `.commitlintrc.js`) are not supported.
+const COMMITLINT_FILES: &[&str] = &[
+    ".commitlintrc.json",
+    ".commitlintrc.yaml",
+    ".commitlintrc.yml",
+    ".commitlintrc",
+];
+
+/// Commitlint rule ids that map onto gitfluff config. Settings of every other rule are ignored
+/// without being parsed, so plugin rules with their own value shapes never fail the import.
+const MAPPED_RULES: &[&str] = &[
+    "type-enum",
+    "scope-enum",
+    "header-max-length",
+    "body-max-line-length",
+    "subject-full-stop",
+];
+
+/// Fill the unset keys of `rules` from a commitlint config in `dir`, if one exists. Keys set in
+/// the gitfluff config always win.
+pub fn import_commitlint(dir: &Path, rules: &mut RulesConfig) -> Result<()> {
+    let Some(path) = COMMITLINT_FILES
+        .iter()
+        .map(|name| dir.join(name))
+        .find(|path| path.is_file())
+    else {
+        return Ok(());
+    };
+    let raw = fs::read_to_string(&path)
+        .with_context(|| format!("failed to read commitlint config {}", path.display()))?;
+    let is_json = path.extension().is_some_and(|ext| ext == "json")
+        || (path.extension().is_none() && raw.trim_start().starts_with('{'));
+    let document: Value = if is_json {
+        serde_json::from_str(&raw)
+            .with_context(|| format!("failed to parse commitlint config {}", path.display()))?
+    } else {
+        serde_yaml_ng::from_str(&raw)
+            .with_context(|| format!("failed to parse commitlint config {}", path.display()))?
+    };
+    let Some(commitlint_rules) = document.get("rules").and_then(Value::as_object) else {
+        return Ok(());
+    };
+
+    for (id, setting) in commitlint_rules {
+        if !MAPPED_RULES.contains(&id.as_str()) {
+            continue;
+        }
+        let rule = CommitlintRule::parse(setting)
+            .with_context(|| format!("invalid commitlint rule `{id}` in {}", path.display()))?;
+        let mapped = match id.as_str() {
+            "type-enum" => {
+                if rule.always && rules.allowed_types.is_none() {
+                    rules.allowed_types = rule.strings();
+                }
+                true
+            }
+            "scope-enum" => {
+                if rule.always && rules.allowed_scopes.is_none() {
+                    rules.allowed_scopes = rule.strings();
+                }
+                true
+            }
+            // `[level, "always", 72]` caps the whole header (type, scope, and subject), just like
+            // gitfluff's `header_max_length`.
+            "header-max-length" => {
+                if rule.always && rules.header_max_length.is_none() {
+                    rules.header_max_length = rule.length();
+                }
+                true
+            }
+            // `[level, "always", 100]` caps every body line; footer lines keep their own limit.
+            "body-max-line-length" => {
+                if rule.always && rules.body_max_line_length.is_none() {
+                    rules.body_max_line_length = rule.length();
+                }
+                true
+            }
+            // gitfluff always forbids a trailing full stop; only the level carries over.
+            "subject-full-stop" => !rule.always,
+            _ => false,
+        };
+        if mapped {
+            rules.severity.entry(id.clone()).or_insert(rule.severity);
+        }
+    }
+    Ok(())
+}
+
+/// A commitlint rule setting: `[level, "always" | "never", value]`.
+struct CommitlintRule {
+    severity: Severity,
+    always: bool,
+    value: Option<Value>,
+}
+
+impl CommitlintRule {
+    fn parse(setting: &Value) -> Result<Self> {
+        let Some(parts) = setting.as_array() else {
+            bail!("expected `[level, applicable, value]`");
+        };
+        let severity = match parts.first().and_then(Value::as_u64) {
+            Some(0) => Severity::Off,
+            Some(1) => Severity::Warn,
+            Some(2) => Severity::Error,
+            _ => bail!("level must be 0, 1, or 2"),
+        };
+        let always = match parts.get(1).and_then(Value::as_str) {
+            None | Some("always") => true,
+            Some("never") => false,
+            Some(other) => bail!("applicable must be `always` or `never`, got `{other}`"),
+        };
+        Ok(Self {
+            severity,
+            always,
+            value: parts.get(2).cloned(),
+        })
+    }
+
+    fn strings(&self) -> Option<Vec<String>> {
+        self.value.as_ref()?.as_array().map(|items| {
+            items
+                .iter()
+                .filter_map(|item| item.as_str().map(str::to_string))
+                .collect()
+        })
+    }
+
+    fn length(&self) -> Option<usize> {
+        self.value
+            .as_ref()?
+            .as_u64()
+            .and_then(|len| usize::try_from(len).ok())
+    }
+}
@@ -1,56 +1,232 @@
+use std::collections::BTreeMap;
 use std::fs;
-use std::path::{Path, PathBuf};
+use std::path::{Component, Path, PathBuf};
 
-use anyhow::{Context, Result};
+use anyhow::{Context, Result, anyhow, bail};
+use schemars::{JsonSchema, Schema, schema_for};
 use serde::Deserialize;
 
-#[derive(Debug, Deserialize, Default)]
-#[serde(default)]
+use crate::lint::{
+    BulletStyle, CleanupMode, HeaderSeparator, LengthUnit, LineEnding, ScopeCase, Severity,
+    SubjectCase, SubjectCaseScope, validate_rule_ids,
+};
+
+#[derive(Debug, Deserialize, Default, JsonSchema)]
+#[serde(default, deny_unknown_fields)]
+#[schemars(transform = add_extends_property)]
 pub struct FileConfig {
     pub preset: Option<String>,
     pub write: Option<bool>,
     pub rules: RulesConfig,
 }
 
-#[derive(Debug, Deserialize, Default)]
-#[serde(default)]
+#[derive(Debug, Deserialize, Default, JsonSchema)]
+#[serde(default, deny_unknown_fields)]
 pub struct RulesConfig {
     pub message: Option<MessageRuleConfig>,
     pub excludes: Vec<ExcludeRuleConfig>,
     pub cleanup: Vec<CleanupRuleConfig>,
+    /// TOML file (relative to this config, or absolute) whose `excludes` / `cleanup` arrays are
+    /// appended to this table's. Only `load_config` reads it; `validate` rejects a config that
+    /// still sets it.
+    pub rules_file: Option<String>,
     pub single_line: Option<bool>,
     pub require_body: Option<bool>,
+    /// Accepted Conventional Commit types, replacing the preset's list.
+    pub allowed_types: Option<Vec<String>>,
+    /// Conventional types that require a body (`["feat", "fix"]`).
+    pub require_body_for: Vec<String>,
+    /// Conventional types whose commits must not have a body.
+    pub forbid_body_for: Vec<String>,
     pub exit_nonzero_on_rewrite: Option<bool>,
+    pub quiet: Option<bool>,
     pub no_emojis: Option<bool>,
+    /// Template tokens (e.g. `<subject>`) that must not survive into a commit message.
+    pub forbid_placeholders: Vec<String>,
+    /// Forbid emoji in the header line only.
+    pub no_emoji_header: Option<bool>,
     pub ascii_only: Option<bool>,
     pub title_prefix: Option<String>,
     pub title_prefix_separator: Option<String>,
     pub title_suffix: Option<String>,
     pub title_suffix_separator: Option<String>,
+    pub subject_lowercase_first: Option<bool>,
+    pub no_issue_ref_in_subject: Option<bool>,
+    /// Required separator between type and subject: `": "` (default) or `":"`.
+    pub header_separator: Option<HeaderSeparator>,
+    /// Fail messages that contain credential-shaped strings (AWS keys, GitHub tokens, ...).
+    pub detect_secrets: Option<bool>,
+    /// Fail `wip:` / `WIP` / `[WIP]` headers.
+    pub no_wip: Option<bool>,
+    /// Fail subjects with an internal `. ` sentence break.
+    pub single_sentence_subject: Option<bool>,
+    /// On `--write`, move a trailing `(#123)` from the subject into a `Refs:` footer.
+    pub move_trailing_ref: Option<bool>,
+    pub normalize_unicode: Option<bool>,
+    pub subject_case_scope: Option<SubjectCaseScope>,
+    /// First-letter casing of non-conventional titles: `any` (default), `lower`, `upper-first`.
+    pub subject_case: Option<SubjectCase>,
+    /// Conventional scope casing: `any` (default), `lower`, or `kebab`.
+    pub scope_case: Option<ScopeCase>,
+    /// `all` (default) applies every cleanup rule; `first-match` stops after the first that
+    /// changes the message.
+    pub cleanup_mode: Option<CleanupMode>,
+    pub length_unit: Option<LengthUnit>,
+    /// Autofix type aliases (`feature = "feat"`).
+    pub type_aliases: BTreeMap<String, String>,
+    /// Per-rule severity overrides keyed by rule id.
+    pub severity: BTreeMap<String, Severity>,
+    pub line_ending: Option<LineEnding>,
+    pub autofix_only: Option<Vec<String>>,
+    pub body_wrap: Option<usize>,
+    pub body_max_lines: Option<usize>,
+    pub body_bullet_style: Option<BulletStyle>,
+    pub body_capitalized: Option<bool>,
+    pub no_redundant_body_first_line: Option<bool>,
+    pub revert_requires_reason: Option<bool>,
+    pub validate_squash_bullets: Option<bool>,
+    pub subject_max_length: Option<usize>,
+    pub breaking_requires_footer: Option<bool>,
+    pub allow_tabs_in_header: Option<bool>,
+    /// Skip messages with a git-generated merge header (default: true).
+    pub allow_merge_commits: Option<bool>,
+    /// Lint merge commits, both while `MERGE_HEAD` exists and in `--range`, instead of skipping them.
+    pub lint_merges: Option<bool>,
+    /// With `lint_merges`, still skip octopus merges (more than two parents).
+    pub skip_octopus_merges: Option<bool>,
+    /// Co-authors (`Name <email>`) that cleanup rules must keep.
+    pub preserve_coauthors: Vec<String>,
+    /// Accepted Conventional Commit scopes; unset allows any scope.
+    pub allowed_scopes: Option<Vec<String>>,
+    /// Accepted footer tokens; unset allows any well-formed token.
+    pub allowed_footer_tokens: Option<Vec<String>>,
+    pub max_footers: Option<usize>,
+    /// Cap on the whole message in UTF-8 bytes (not characters).
+    pub max_message_bytes: Option<usize>,
+    pub strict_blank_lines: Option<bool>,
+    pub breaking_change_last: Option<bool>,
+    /// Author names whose commits `--range` skips instead of linting (`["dependabot[bot]"]`).
+    pub range_skip_authors: Vec<String>,
+    /// Regex; `--range` skips commits whose message matches it.
+    pub range_skip_pattern: Option<String>,
+    pub header_max_length: Option<usize>,
+    pub body_max_line_length: Option<usize>,
+    /// `"50/72"` sets `subject_max_length` and `body_max_line_length` unless they are set too.
+    pub convention: Option<Convention>,
+    /// Fill unset keys from a `.commitlintrc.json` / `.commitlintrc.yaml` next to the config.
+    pub import_commitlint: Option<bool>,
+    /// Message to lint when no source is passed; `error` (default) keeps requiring one.
+    pub default_source: Option<DefaultSource>,
 }
 
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Deserialize, Clone, JsonSchema)]
+#[serde(deny_unknown_fields)]
 pub struct MessageRuleConfig {
-    pub pattern: String,
+    pub pattern: Option<String>,
+    /// Alternative title patterns; a title passes when `pattern` or any of these matches.
+    #[serde(default)]
+    pub patterns: Vec<String>,
     pub description: Option<String>,
+    /// Named capture groups of `pattern` that must not be empty.
+    #[serde(default)]
+    pub require_groups: Vec<String>,
+}
+
+/// What `gitfluff lint` reads when no message source is passed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, JsonSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum DefaultSource {
+    /// Fail with a "no commit message source" error.
+    #[default]
+    Error,
+    /// Lint `COMMIT_EDITMSG` in the repository's git directory.
+    CommitEditmsg,
 }
 
-#[derive(Debug, Deserialize, Clone)]
+/// Named length budgets that fill in several length keys at once.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, JsonSchema)]
+pub enum Convention {
+    /// `subject_max_length = 50` and `body_max_line_length = 72`.
+    #[serde(rename = "50/72")]
+    FiftySeventyTwo,
+}
+
+#[derive(Debug, Deserialize, Clone, JsonSchema)]
+#[serde(deny_unknown_fields)]
 pub struct ExcludeRuleConfig {
     pub pattern: String,
     pub message: Option<String>,
+    /// Where the entry is defined; filled in by `load_config`.
+    #[serde(skip)]
+    #[schemars(skip)]
+    pub defined_at: Option<ConfigLocation>,
 }
 
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Deserialize, Clone, JsonSchema)]
+#[serde(deny_unknown_fields)]
 pub struct CleanupRuleConfig {
     pub find: String,
     pub replace: String,
     pub description: Option<String>,
+    /// Where the entry is defined; filled in by `load_config`.
+    #[serde(skip)]
+    #[schemars(skip)]
+    pub defined_at: Option<ConfigLocation>,
+}
+
+/// The config file and 1-based line where an `excludes` / `cleanup` entry starts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigLocation {
+    pub path: PathBuf,
+    pub line: usize,
+}
+
+/// A config table together with the location of each `excludes` / `cleanup` entry, in array order.
+struct Layer {
+    table: toml::Table,
+    excludes: Vec<ConfigLocation>,
+    cleanup: Vec<ConfigLocation>,
+}
+
+/// JSON Schema (draft 2020-12) describing the config file format, for editor validation.
+pub fn config_schema() -> Schema {
+    schema_for!(FileConfig)
+}
+
+/// `extends` and `branches` are resolved by `load_config` before deserialization, so they are not
+/// struct fields.
+fn add_extends_property(schema: &mut Schema) {
+    if let Some(properties) = schema
+        .get_mut("properties")
+        .and_then(|properties| properties.as_object_mut())
+    {
+        properties.insert(
+            "branches".to_string(),
+            serde_json::json!({
+                "description": "Rule overlays applied when the current git branch matches the glob key.",
+                "type": "object",
+                "additionalProperties": { "$ref": "#/$defs/RulesConfig" }
+            }),
+        );
+        properties.insert(
+            "extends".to_string(),
+            serde_json::json!({
+                "description": "Config file(s) to layer this one on top of, relative to this file.",
+                "anyOf": [
+                    { "type": "string" },
+                    { "type": "array", "items": { "type": "string" } }
+                ]
+            }),
+        );
+    }
 }
 
+/// Load the config (explicit or discovered from `start_dir`), with the `[branches."<glob>"]`
+/// overlays matching `branch` layered over `[rules]`.
 pub fn load_config(
     explicit_path: Option<&Path>,
     start_dir: &Path,
+    branch: Option<&str>,
 ) -> Result<Option<(PathBuf, FileConfig)>> {
     let path = match explicit_path {
         Some(p) => p.to_path_buf(),
@@ -60,13 +236,358 @@ pub fn load_config(
         },
     };
 
-    let content = fs::read_to_string(&path)
-        .with_context(|| format!("failed to read config at {}", path.display()))?;
-    let config: FileConfig = toml::from_str(&content)
+    let mut layer = load_layered(&path, &mut Vec::new())?;
+    apply_branch_overlays(&mut layer.table, branch)
+        .with_context(|| format!("invalid config at {}", path.display()))?;
+    let mut config: FileConfig = layer
+        .table
+        .try_into()
+        .map_err(describe_parse_error)
+        .with_context(|| format!("invalid config at {}", path.display()))?;
+    for (exclude, location) in config.rules.excludes.iter_mut().zip(layer.excludes) {
+        exclude.defined_at = Some(location);
+    }
+    for (cleanup, location) in config.rules.cleanup.iter_mut().zip(layer.cleanup) {
+        cleanup.defined_at = Some(location);
+    }
+    validate_rule_ids(config.rules.severity.keys())
         .with_context(|| format!("invalid config at {}", path.display()))?;
+    if config.rules.rules_file.is_some() {
+        bail!(
+            "invalid config at {}: `rules_file` is not supported in `[branches]` overlays",
+            path.display()
+        );
+    }
+    if let Some(both) = config.rules.forbid_body_for.iter().find(|ty| {
+        config
+            .rules
+            .require_body_for
+            .iter()
+            .any(|required| required.eq_ignore_ascii_case(ty))
+    }) {
+        bail!(
+            "invalid config at {}: `{both}` is listed in both require_body_for and forbid_body_for",
+            path.display()
+        );
+    }
+    if config.rules.body_wrap == Some(0) {
+        bail!(
+            "invalid config at {}: `body_wrap` must be at least 1",
+            path.display()
+        );
+    }
     Ok(Some((path, config)))
 }
 
+/// Read `path` as a TOML table with every `extends` target (a path or list of paths, relative to
+/// the extending file) merged underneath it. `chain` holds the files currently being loaded so a
+/// cycle is reported instead of recursing forever.
+fn load_layered(path: &Path, chain: &mut Vec<PathBuf>) -> Result<Layer> {
+    let canonical = fs::canonicalize(path)
+        .with_context(|| format!("failed to read config at {}", path.display()))?;
+    if chain.contains(&canonical) {
+        let cycle: Vec<String> = chain
+            .iter()
+            .chain(std::iter::once(&canonical))
+            .map(|p| p.display().to_string())
+            .collect();
+        bail!("config `extends` cycle: {}", cycle.join(" -> "));
+    }
+
+    let content = fs::read_to_string(path)
+        .with_context(|| format!("failed to read config at {}", path.display()))?;
+    let mut table: toml::Table = toml::from_str(&content)
+        .with_context(|| format!("invalid config at {}", path.display()))?;
+    let extends = match table.remove("extends") {
+        None => Vec::new(),
+        Some(toml::Value::String(target)) => vec![target],
+        Some(toml::Value::Array(targets)) => targets
+            .into_iter()
+            .map(|target| match target {
+                toml::Value::String(target) => Ok(target),
+                _ => Err(anyhow!("`extends` entries must be strings")),
+            })
+            .collect::<Result<_>>()
+            .with_context(|| format!("invalid config at {}", path.display()))?,
+        Some(_) => bail!(
+            "invalid config at {}: `extends` must be a path or a list of paths",
+            path.display()
+        ),
+    };
+
+    chain.push(canonical);
+    let base_dir = path.parent().unwrap_or_else(|| Path::new("."));
+    let mut merged = Layer {
+        table: toml::Table::new(),
+        excludes: Vec::new(),
+        cleanup: Vec::new(),
+    };
+    for target in extends {
+        let target_path = base_dir.join(&target);
+        if !target_path.is_file() {
+            bail!(
+                "config {} extends `{}`, but {} does not exist",
+                path.display(),
+                target,
+                target_path.display()
+            );
+        }
+        let base = load_layered(&target_path, chain)?;
+        merged.layer(base);
+    }
+    chain.pop();
+
+    let (excludes, cleanup) = rule_entry_locations(path, &content);
+    let mut own = Layer {
+        table,
+        excludes,
+        cleanup,
+    };
+    let rules_file = match own.table.get_mut("rules") {
+        Some(toml::Value::Table(rules)) => rules.remove("rules_file"),
+        _ => None,
+    };
+    match rules_file {
+        None => {}
+        Some(toml::Value::String(target)) => {
+            let rules = load_rules_file(&base_dir.join(&target))
+                .with_context(|| format!("config {} sets `rules_file`", path.display()))?;
+            own.layer(rules);
+        }
+        Some(_) => bail!(
+            "invalid config at {}: `rules_file` must be a path",
+            path.display()
+        ),
+    }
+    merged.layer(own);
+    Ok(merged)
+}
+
+/// Read a `rules_file`: a TOML file holding only top-level `excludes` and `cleanup` arrays, returned
+/// as a layer whose entries sit under `[rules]`.
+fn load_rules_file(path: &Path) -> Result<Layer> {
+    #[derive(Deserialize)]
+    #[serde(deny_unknown_fields)]
+    struct RulesFile {
+        #[serde(default)]
+        excludes: Vec<toml::Spanned<toml::Value>>,
+        #[serde(default)]
+        cleanup: Vec<toml::Spanned<toml::Value>>,
+    }
+
+    let content = fs::read_to_string(path)
+        .with_context(|| format!("failed to read rules file {}", path.display()))?;
+    let invalid = || format!("invalid rules file {}", path.display());
+    let file: RulesFile = toml::from_str(&content)
+        .map_err(describe_parse_error)
+        .with_context(invalid)?;
+    // Check each entry's shape here so a bad one is reported against the rules file rather than
+    // the config that pulled it in.
+    for entry in &file.excludes {
+        entry
+            .get_ref()
+            .clone()
+            .try_into::<ExcludeRuleConfig>()
+            .map_err(describe_parse_error)
+            .with_context(invalid)?;
+    }
+    for entry in &file.cleanup {
+        entry
+            .get_ref()
+            .clone()
+            .try_into::<CleanupRuleConfig>()
+            .map_err(describe_parse_error)
+            .with_context(invalid)?;
+    }
+
+    let excludes = entry_locations(path, &content, &file.excludes);
+    let cleanup = entry_locations(path, &content, &file.cleanup);
+    let values = |entries: Vec<toml::Spanned<toml::Value>>| {
+        toml::Value::Array(entries.into_iter().map(toml::Spanned::into_inner).collect())
+    };
+    let mut rules = toml::Table::new();
+    if !file.excludes.is_empty() {
+        rules.insert("excludes".to_string(), values(file.excludes));
+    }
+    if !file.cleanup.is_empty() {
+        rules.insert("cleanup".to_string(), values(file.cleanup));
+    }
+    let mut wrapper = toml::Table::new();
+    wrapper.insert("rules".to_string(), toml::Value::Table(rules));
+    Ok(Layer {
+        table: wrapper,
+        excludes,
+        cleanup,
+    })
+}
+
+impl Layer {
+    fn layer(&mut self, overlay: Layer) {
+        merge_tables(&mut self.table, overlay.table);
+        self.excludes.extend(overlay.excludes);
+        self.cleanup.extend(overlay.cleanup);
+    }
+}
+
+/// Remove the `branches` table and merge every overlay whose glob matches `branch` into `rules`.
+/// Detached HEADs (`branch` is `None`) keep the base rules.
+fn apply_branch_overlays(table: &mut toml::Table, branch: Option<&str>) -> Result<()> {
+    let overlays = match table.remove("branches") {
+        None => return Ok(()),
+        Some(toml::Value::Table(overlays)) => overlays,
+        Some(_) => bail!("`branches` must be a table of branch globs"),
+    };
+    for (glob, overlay) in overlays {
+        let toml::Value::Table(overlay) = overlay else {
+            bail!("`branches.\"{glob}\"` must be a table of rules");
+        };
+        if branch.is_some_and(|branch| glob_matches(&glob, branch)) {
+            let rules = table
+                .entry("rules")
+                .or_insert_with(|| toml::Value::Table(toml::Table::new()));
+            if let toml::Value::Table(rules) = rules {
+                merge_tables(rules, overlay);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Shell-style glob match where `*` matches any run of characters (including `/`) and `?` one.
+fn glob_matches(glob: &str, text: &str) -> bool {
+    let glob: Vec<char> = glob.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+    let (mut g, mut t) = (0, 0);
+    let mut backtrack: Option<(usize, usize)> = None;
+    while t < text.len() {
+        match glob.get(g) {
+            Some('*') => {
+                backtrack = Some((g, t));
+                g += 1;
+            }
+            Some(&c) if c == '?' || c == text[t] => {
+                g += 1;
+                t += 1;
+            }
+            _ => match backtrack {
+                Some((star, matched)) => {
+                    g = star + 1;
+                    t = matched + 1;
+                    backtrack = Some((star, matched + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+    glob[g..].iter().all(|&c| c == '*')
+}
+
+/// Locate each `rules.excludes` and `rules.cleanup` entry in `content` with a span-aware parse.
+/// Shape errors are left for the real deserialization to report, so they yield no locations.
+fn rule_entry_locations(path: &Path, content: &str) -> (Vec<ConfigLocation>, Vec<ConfigLocation>) {
+    #[derive(Deserialize, Default)]
+    #[serde(default)]
+    struct Probe {
+        rules: RulesProbe,
+    }
+
+    #[derive(Deserialize, Default)]
+    #[serde(default)]
+    struct RulesProbe {
+        excludes: Vec<toml::Spanned<toml::Value>>,
+        cleanup: Vec<toml::Spanned<toml::Value>>,
+    }
+
+    let Ok(probe) = toml::from_str::<Probe>(content) else {
+        return (Vec::new(), Vec::new());
+    };
+    (
+        entry_locations(path, content, &probe.rules.excludes),
+        entry_locations(path, content, &probe.rules.cleanup),
+    )
+}
+
+fn entry_locations(
+    path: &Path,
+    content: &str,
+    entries: &[toml::Spanned<toml::Value>],
+) -> Vec<ConfigLocation> {
+    entries
+        .iter()
+        .map(|entry| ConfigLocation {
+            path: path.to_path_buf(),
+            line: content[..entry.span().start].matches('\n').count() + 1,
+        })
+        .collect()
+}
+
+/// Layer `overlay` on top of `base`: tables merge key by key, `excludes` and `cleanup` arrays are
+/// concatenated, and every other value is replaced.
+fn merge_tables(base: &mut toml::Table, overlay: toml::Table) {
+    for (key, value) in overlay {
+        match (base.get_mut(&key), value) {
+            (Some(toml::Value::Table(existing)), toml::Value::Table(value)) => {
+                merge_tables(existing, value);
+            }
+            (Some(toml::Value::Array(existing)), toml::Value::Array(value))
+                if key == "excludes" || key == "cleanup" =>
+            {
+                existing.extend(value);
+            }
+            (_, value) => {
+                base.insert(key, value);
+            }
+        }
+    }
+}
+
+/// Turn serde's "unknown field" error into one that names the key and suggests the closest known
+/// key; other parse errors pass through unchanged.
+fn describe_parse_error(err: toml::de::Error) -> anyhow::Error {
+    let Some((key, expected)) = err
+        .message()
+        .strip_prefix("unknown field `")
+        .and_then(|rest| rest.split_once('`'))
+    else {
+        return err.into();
+    };
+
+    let known: Vec<&str> = expected
+        .split('`')
+        .skip(1)
+        .step_by(2)
+        .filter(|name| !name.is_empty())
+        .collect();
+    match closest_key(key, &known) {
+        Some(suggestion) => anyhow!("unknown config key `{key}`; did you mean `{suggestion}`?"),
+        None => anyhow!("unknown config key `{key}`"),
+    }
+}
+
+fn closest_key<'a>(key: &str, known: &[&'a str]) -> Option<&'a str> {
+    let max_distance = (key.chars().count() / 3).max(2);
+    known
+        .iter()
+        .map(|candidate| (levenshtein(key, candidate), *candidate))
+        .filter(|(distance, _)| *distance <= max_distance)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate)
+}
+
+fn levenshtein(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1; b.len() + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != *cb);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
 fn find_config(start_dir: &Path) -> Option<PathBuf> {
     let mut current = start_dir;
     loop {
@@ -83,3 +604,40 @@ fn find_config(start_dir: &Path) -> Option<PathBuf> {
     }
     None
 }
+
+/// Directory that config discovery should start from when the commit touches `changed` paths
+/// (relative to the worktree root, as printed by `git diff --cached --name-only`). This is the
+/// deepest directory shared by every changed path, so a commit confined to one package picks up
+/// that package's config. Returns `None` when there is nothing to go on.
+pub fn changed_files_start_dir(start_dir: &Path, changed: &[PathBuf]) -> Option<PathBuf> {
+    let mut common: Option<Vec<Component<'_>>> = None;
+    for path in changed {
+        let parent: Vec<Component<'_>> = path
+            .parent()
+            .map(|dir| dir.components().collect())
+            .unwrap_or_default();
+        common = Some(match common {
+            None => parent,
+            Some(prefix) => prefix
+                .into_iter()
+                .zip(parent)
+                .take_while(|(a, b)| a == b)
+                .map(|(a, _)| a)
+                .collect(),
+        });
+    }
+
+    let common: PathBuf = common?.into_iter().collect();
+    let root = find_worktree_root(start_dir).unwrap_or_else(|| start_dir.to_path_buf());
+    Some(root.join(common))
+}
+
+fn find_worktree_root(start_dir: &Path) -> Option<PathBuf> {
+    let mut current = start_dir;
+    loop {
+        if current.join(".git").exists() {
+            return Some(current.to_path_buf());
+        }
+        current = current.parent()?;
+    }
+}
@@ -0,0 +1,136 @@
+const CONTEXT_LINES: usize = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DiffKind {
+    Equal,
+    Delete,
+    Insert,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct DiffLine<'a> {
+    kind: DiffKind,
+    text: &'a str,
+}
+
+/// Render a unified diff (3 lines of context) between two messages. Returns an empty string when
+/// both sides contain the same lines.
+pub fn unified_diff(old: &str, new: &str, label: &str) -> String {
+    let old_lines: Vec<&str> = old.lines().collect();
+    let new_lines: Vec<&str> = new.lines().collect();
+    let ops = diff_lines(&old_lines, &new_lines);
+
+    let changed: Vec<usize> = ops
+        .iter()
+        .enumerate()
+        .filter(|(_, op)| op.kind != DiffKind::Equal)
+        .map(|(idx, _)| idx)
+        .collect();
+    if changed.is_empty() {
+        return String::new();
+    }
+
+    let mut out = format!("--- a/{label}\n+++ b/{label}\n");
+    let mut group_start = 0;
+    while group_start < changed.len() {
+        let mut group_end = group_start;
+        while group_end + 1 < changed.len()
+            && changed[group_end + 1] - changed[group_end] <= CONTEXT_LINES * 2
+        {
+            group_end += 1;
+        }
+
+        let start = changed[group_start].saturating_sub(CONTEXT_LINES);
+        let end = (changed[group_end] + CONTEXT_LINES + 1).min(ops.len());
+        render_hunk(&mut out, &ops, start, end);
+        group_start = group_end + 1;
+    }
+
+    out
+}
+
+fn render_hunk(out: &mut String, ops: &[DiffLine<'_>], start: usize, end: usize) {
+    let old_before = ops[..start]
+        .iter()
+        .filter(|op| op.kind != DiffKind::Insert)
+        .count();
+    let new_before = ops[..start]
+        .iter()
+        .filter(|op| op.kind != DiffKind::Delete)
+        .count();
+    let hunk = &ops[start..end];
+    let old_count = hunk.iter().filter(|op| op.kind != DiffKind::Insert).count();
+    let new_count = hunk.iter().filter(|op| op.kind != DiffKind::Delete).count();
+
+    out.push_str(&format!(
+        "@@ -{} +{} @@\n",
+        hunk_range(old_before, old_count),
+        hunk_range(new_before, new_count)
+    ));
+    for op in hunk {
+        let marker = match op.kind {
+            DiffKind::Equal => ' ',
+            DiffKind::Delete => '-',
+            DiffKind::Insert => '+',
+        };
+        out.push(marker);
+        out.push_str(op.text);
+        out.push('\n');
+    }
+}
+
+fn hunk_range(before: usize, count: usize) -> String {
+    if count == 0 {
+        format!("{before},0")
+    } else {
+        format!("{},{count}", before + 1)
+    }
+}
+
+fn diff_lines<'a>(old: &[&'a str], new: &[&'a str]) -> Vec<DiffLine<'a>> {
+    // Commit messages are short, so a plain LCS table is plenty.
+    let mut lcs = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lcs[i][j] = if old[i] == new[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut ops = Vec::with_capacity(old.len() + new.len());
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() && j < new.len() {
+        if old[i] == new[j] {
+            ops.push(DiffLine {
+                kind: DiffKind::Equal,
+                text: old[i],
+            });
+            i += 1;
+            j += 1;
+        } else if lcs[i + 1][j] >= lcs[i][j + 1] {
+            ops.push(DiffLine {
+                kind: DiffKind::Delete,
+                text: old[i],
+            });
+            i += 1;
+        } else {
+            ops.push(DiffLine {
+                kind: DiffKind::Insert,
+                text: new[j],
+            });
+            j += 1;
+        }
+    }
+    ops.extend(old[i..].iter().map(|text| DiffLine {
+        kind: DiffKind::Delete,
+        text,
+    }));
+    ops.extend(new[j..].iter().map(|text| DiffLine {
+        kind: DiffKind::Insert,
+        text,
+    }));
+    ops
+}
@@ -0,0 +1,92 @@
+use anyhow::{Result, anyhow, bail};
+use encoding_rs::{Encoding, UTF_8, UTF_16BE, UTF_16LE};
+
+/// How a commit message file was encoded on disk, so `--write` can store it the same way.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TextEncoding {
+    pub encoding: &'static Encoding,
+    pub bom: bool,
+}
+
+impl Default for TextEncoding {
+    fn default() -> Self {
+        Self {
+            encoding: UTF_8,
+            bom: false,
+        }
+    }
+}
+
+/// Decode message bytes. A UTF-8 or UTF-16 byte-order mark wins over `label`; without either the
+/// bytes must be valid UTF-8. The BOM never reaches the linted text. Errors read as a predicate
+/// (`is not valid UTF-8; ...`) for the caller to prefix with the message source.
+pub fn decode(bytes: &[u8], label: Option<&str>) -> Result<(String, TextEncoding)> {
+    if let Some((encoding, bom_len)) = Encoding::for_bom(bytes) {
+        let text = decode_strict(encoding, &bytes[bom_len..])?;
+        return Ok((
+            text,
+            TextEncoding {
+                encoding,
+                bom: true,
+            },
+        ));
+    }
+
+    let encoding = match label {
+        Some(label) => Encoding::for_label(label.trim().as_bytes())
+            .ok_or_else(|| anyhow!("cannot be decoded with unknown encoding `{label}`"))?,
+        None => UTF_8,
+    };
+    let text = decode_strict(encoding, bytes).map_err(|err| {
+        if label.is_none() {
+            anyhow!("{err}; set --encoding if it uses a different encoding")
+        } else {
+            err
+        }
+    })?;
+    Ok((
+        text,
+        TextEncoding {
+            encoding,
+            bom: false,
+        },
+    ))
+}
+
+fn decode_strict(encoding: &'static Encoding, bytes: &[u8]) -> Result<String> {
+    encoding
+        .decode_without_bom_handling_and_without_replacement(bytes)
+        .map(|text| text.into_owned())
+        .ok_or_else(|| anyhow!("is not valid {}", encoding.name()))
+}
+
+/// Encode `text` back into `target`, prefixing the byte-order mark when `target.bom` is set.
+pub fn encode(text: &str, target: TextEncoding) -> Result<Vec<u8>> {
+    let TextEncoding { encoding, bom } = target;
+    let mut out = Vec::new();
+    // encoding_rs only encodes to ASCII-compatible encodings, so UTF-16 is written by hand.
+    if encoding == UTF_16LE || encoding == UTF_16BE {
+        let little_endian = encoding == UTF_16LE;
+        for unit in bom.then_some(0xFEFF).into_iter().chain(text.encode_utf16()) {
+            out.extend(if little_endian {
+                unit.to_le_bytes()
+            } else {
+                unit.to_be_bytes()
+            });
+        }
+        return Ok(out);
+    }
+
+    if bom && encoding == UTF_8 {
+        out.extend_from_slice(b"\xEF\xBB\xBF");
+    }
+    let (bytes, _, unmappable) = encoding.encode(text);
+    if unmappable {
+        bail!(
+            "rewritten message cannot be represented in {}",
+            encoding.name()
+        );
+    }
+    out.extend_from_slice(&bytes);
+    Ok(out)
+}
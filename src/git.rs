@@ -0,0 +1,111 @@
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use anyhow::{Context, Result, anyhow, bail};
+use regex::Regex;
+
+#[derive(Debug, Clone)]
+pub struct Commit {
+    pub sha: String,
+    pub author: String,
+    pub message: String,
+}
+
+impl Commit {
+    pub fn short_sha(&self) -> &str {
+        &self.sha[..self.sha.len().min(7)]
+    }
+}
+
+/// Which commits of a range are linted: merges only with `include_merges`, and never those by
+/// `skip_authors` or whose message matches `skip_pattern`.
+#[derive(Debug, Clone, Default)]
+pub struct RangeFilter {
+    pub include_merges: bool,
+    pub skip_authors: Vec<String>,
+    pub skip_pattern: Option<Regex>,
+}
+
+impl RangeFilter {
+    /// Why `commit` is skipped, or `None` when it should be linted.
+    pub fn skip_reason(&self, commit: &Commit) -> Option<String> {
+        if self.skip_authors.contains(&commit.author) {
+            return Some(format!("author `{}`", commit.author));
+        }
+        self.skip_pattern
+            .as_ref()
+            .filter(|pattern| pattern.is_match(&commit.message))
+            .map(|pattern| format!("message matches `{}`", pattern.as_str()))
+    }
+}
+
+/// Collect the commits in `range` (oldest first) together with their authors and raw messages.
+/// Merge commits are left out unless `include_merges` is set.
+pub fn commits_in_range(repo_dir: &Path, range: &str, include_merges: bool) -> Result<Vec<Commit>> {
+    let mut args = vec!["rev-list", "--reverse"];
+    if !include_merges {
+        args.push("--no-merges");
+    }
+    args.push(range);
+    let shas = run_git(repo_dir, &args)
+        .with_context(|| format!("failed to list commits in range `{range}`"))?;
+
+    shas.lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|sha| read_commit(repo_dir, sha.trim()))
+        .collect()
+}
+
+/// The commit `HEAD` points at; fails with a clear message in a repository without commits.
+pub fn head_commit(repo_dir: &Path) -> Result<Commit> {
+    let sha = run_git(repo_dir, &["rev-parse", "--verify", "--quiet", "HEAD"])
+        .map_err(|_| anyhow!("the repository has no commits yet"))?;
+    read_commit(repo_dir, sha.trim())
+}
+
+fn read_commit(repo_dir: &Path, sha: &str) -> Result<Commit> {
+    // Ask for UTF-8 whatever `i18n.logOutputEncoding` says; git re-encodes the message for us.
+    let log = run_git(
+        repo_dir,
+        &["log", "-1", "--encoding=UTF-8", "--format=%an%x00%B", sha],
+    )
+    .with_context(|| format!("failed to read commit message for {sha}"))?;
+    let (author, body) = log.split_once('\0').unwrap_or(("", &log));
+    Ok(Commit {
+        sha: sha.to_string(),
+        author: author.to_string(),
+        message: format!("{}\n", body.trim_end()),
+    })
+}
+
+/// Where the repository at `repo_dir` keeps `name` (`git rev-parse --git-path`), e.g. `hooks`.
+pub fn git_path(repo_dir: &Path, name: &str) -> Result<PathBuf> {
+    let path = run_git(repo_dir, &["rev-parse", "--git-path", name])?;
+    Ok(repo_dir.join(path.trim_end_matches(['\r', '\n'])))
+}
+
+/// The effective value of git config `key` for `repo_dir`, or `None` when it is unset or empty.
+pub fn config_value(repo_dir: &Path, key: &str) -> Option<String> {
+    run_git(repo_dir, &["config", "--get", key])
+        .ok()
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+fn run_git(repo_dir: &Path, args: &[&str]) -> Result<String> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(repo_dir)
+        .output()
+        .context("failed to run git")?;
+    if !output.status.success() {
+        bail!(
+            "git {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    // A message git can't re-encode (raw bytes without an encoding header) is still linted, with
+    // the undecodable bytes shown as U+FFFD.
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
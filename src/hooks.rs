@@ -4,15 +4,28 @@ use std::path::{Path, PathBuf};
 use anyhow::{Context, Result, bail};
 use clap::ValueEnum;
 
+use crate::git::{config_value, git_path};
+
 #[derive(Debug, Clone, Copy, ValueEnum)]
 pub enum HookKind {
     #[clap(name = "commit-msg")]
     CommitMsg,
+    #[clap(name = "pre-push")]
+    PrePush,
 }
 
-pub fn install_hook(start_dir: &Path, kind: HookKind, write: bool, force: bool) -> Result<PathBuf> {
-    let git_dir = locate_git_dir(start_dir).context("failed to locate .git directory")?;
-    let hooks_dir = git_dir.join("hooks");
+/// Write the gitfluff hook script. An existing hook is kept only with `chain`: it is renamed to
+/// `<hook>.local` and the new script runs it first, stopping on its failure. A `robust`
+/// commit-msg hook falls back to `COMMIT_EDITMSG` when git passes no usable message file.
+pub fn install_hook(
+    start_dir: &Path,
+    kind: HookKind,
+    write: bool,
+    force: bool,
+    chain: bool,
+    robust: bool,
+) -> Result<PathBuf> {
+    let hooks_dir = hooks_dir(start_dir)?.path;
     fs::create_dir_all(&hooks_dir).with_context(|| {
         format!(
             "failed to ensure hooks directory at {}",
@@ -23,15 +36,33 @@ pub fn install_hook(start_dir: &Path, kind: HookKind, write: bool, force: bool)
     let hook_name = hook_filename(kind);
     let hook_path = hooks_dir.join(hook_name);
 
-    if hook_path.exists() && !force {
+    let existing = fs::read_to_string(&hook_path).ok();
+    // Re-installing over our own hook just replaces it; chaining would run gitfluff twice.
+    let chain = chain
+        && existing
+            .as_deref()
+            .is_some_and(|script| !script.contains(hook_marker(kind)));
+    // Built before anything moves, so rejected arguments never leave the existing hook renamed.
+    let script = hook_script(kind, write, chain, robust)?;
+    if chain {
+        let local_path = hooks_dir.join(format!("{hook_name}.local"));
+        if local_path.exists() {
+            bail!(
+                "cannot chain hook `{}`: {} already exists",
+                hook_name,
+                local_path.display()
+            );
+        }
+        fs::rename(&hook_path, &local_path)
+            .with_context(|| format!("failed to move existing hook to {}", local_path.display()))?;
+    } else if hook_path.exists() && !force {
         bail!(
-            "hook `{}` already exists at {} (use --force to overwrite)",
+            "hook `{}` already exists at {} (use --force to overwrite or --chain to keep it)",
             hook_name,
             hook_path.display()
         );
     }
 
-    let script = hook_script(kind, write)?;
     fs::write(&hook_path, script)
         .with_context(|| format!("failed to write hook to {}", hook_path.display()))?;
     apply_executable_permissions(&hook_path)?;
@@ -39,67 +70,149 @@ pub fn install_hook(start_dir: &Path, kind: HookKind, write: bool, force: bool)
     Ok(hook_path)
 }
 
-fn locate_git_dir(start_dir: &Path) -> Result<PathBuf> {
-    let mut current = start_dir;
+/// Remove a hook written by `install_hook`. Hooks without gitfluff's invocation are left alone
+/// unless `force` is set. When the removed hook chained a `<hook>.local`, that original hook is
+/// moved back into place; the returned flag says whether that happened.
+pub fn uninstall_hook(start_dir: &Path, kind: HookKind, force: bool) -> Result<(PathBuf, bool)> {
+    let hook_name = hook_filename(kind);
+    let hooks_dir = hooks_dir(start_dir)?.path;
+    let hook_path = hooks_dir.join(hook_name);
 
-    loop {
-        let candidate = current.join(".git");
-        if candidate.is_dir() {
-            return Ok(candidate);
-        }
-        if candidate.is_file() {
-            return resolve_gitdir_file(&candidate);
-        }
-        match current.parent() {
-            Some(parent) => current = parent,
-            None => bail!("no .git directory found from {}", start_dir.display()),
-        }
+    if !hook_path.is_file() {
+        bail!(
+            "no `{}` hook installed at {}",
+            hook_name,
+            hook_path.display()
+        );
+    }
+    let script = fs::read_to_string(&hook_path).unwrap_or_default();
+    if !force && !script.contains(hook_marker(kind)) {
+        bail!(
+            "hook `{}` at {} was not installed by gitfluff (use --force to remove it anyway)",
+            hook_name,
+            hook_path.display()
+        );
     }
-}
 
-fn resolve_gitdir_file(git_file: &Path) -> Result<PathBuf> {
-    let content = fs::read_to_string(git_file)
-        .with_context(|| format!("failed to read gitdir file {}", git_file.display()))?;
-    let content = content.trim();
-
-    let prefix = "gitdir:";
-    if let Some(rest) = content.strip_prefix(prefix) {
-        let raw = rest.trim();
-        let path = Path::new(raw);
-        let resolved = if path.is_absolute() {
-            path.to_path_buf()
-        } else {
-            git_file
-                .parent()
-                .context("gitdir file missing parent")?
-                .join(path)
-                .canonicalize()
-                .with_context(|| format!("failed to resolve gitdir path {}", path.display()))?
-        };
-        Ok(resolved)
-    } else {
-        bail!("unexpected gitdir file format in {}", git_file.display());
+    fs::remove_file(&hook_path)
+        .with_context(|| format!("failed to remove hook at {}", hook_path.display()))?;
+    let local_name = format!("{hook_name}.local");
+    let local_path = hooks_dir.join(&local_name);
+    let restore = script.contains(hook_marker(kind))
+        && script.contains(&format!("/{local_name}\""))
+        && local_path.is_file();
+    if restore {
+        fs::rename(&local_path, &hook_path).with_context(|| {
+            format!(
+                "failed to restore chained hook {} to {}",
+                local_path.display(),
+                hook_path.display()
+            )
+        })?;
     }
+    Ok((hook_path, restore))
+}
+
+/// Where git looks for hooks, and whether that came from `core.hooksPath`.
+pub struct HooksDir {
+    pub path: PathBuf,
+    pub from_hooks_path: bool,
+}
+
+/// The repository's hooks directory as git itself resolves it (`git rev-parse --git-path
+/// hooks`), so `core.hooksPath` from any config file, includes, and worktrees are honored.
+pub fn hooks_dir(start_dir: &Path) -> Result<HooksDir> {
+    let path = git_path(start_dir, "hooks").context("failed to locate the git hooks directory")?;
+    Ok(HooksDir {
+        path,
+        from_hooks_path: config_value(start_dir, "core.hooksPath").is_some(),
+    })
 }
 
 fn hook_filename(kind: HookKind) -> &'static str {
     match kind {
         HookKind::CommitMsg => "commit-msg",
+        HookKind::PrePush => "pre-push",
+    }
+}
+
+/// The gitfluff invocation every script from `hook_script` contains.
+fn hook_marker(kind: HookKind) -> &'static str {
+    match kind {
+        HookKind::CommitMsg => "exec gitfluff lint",
+        HookKind::PrePush => "gitfluff lint --range",
     }
 }
 
-fn hook_script(kind: HookKind, write: bool) -> Result<String> {
+// Git feeds `<local ref> <local sha> <remote ref> <remote sha>` lines on stdin. An all-zero
+// local sha is a branch deletion; an all-zero remote sha is a new branch, which is linted from
+// its merge base with the remote's default branch (or its whole history when there is none).
+const PRE_PUSH_SCRIPT: &str = r#"remote="$1"
+while read -r local_ref local_sha remote_ref remote_sha; do
+  case "$local_sha" in
+    *[!0]*) ;;
+    *) continue ;;
+  esac
+  case "$remote_sha" in
+    *[!0]*) range="$remote_sha..$local_sha" ;;
+    *)
+      base=$(git merge-base "$local_sha" "refs/remotes/$remote/HEAD" 2>/dev/null)
+      if [ -n "$base" ]; then
+        range="$base..$local_sha"
+      else
+        range="$local_sha"
+      fi
+      ;;
+  esac
+  gitfluff lint --range "$range" || exit 1
+done
+exit 0
+"#;
+
+const ROBUST_MESSAGE_FILE: &str = r#"msg_file="$1"
+if [ -z "$msg_file" ] || [ ! -f "$msg_file" ]; then
+  msg_file="$(git rev-parse --git-dir)/COMMIT_EDITMSG"
+fi
+"#;
+
+fn hook_script(kind: HookKind, write: bool, chain: bool, robust: bool) -> Result<String> {
     let base = match kind {
         HookKind::CommitMsg => {
-            if write {
-                "exec gitfluff lint \"$1\" --write\n"
+            let (prelude, file) = if robust {
+                (ROBUST_MESSAGE_FILE, "\"$msg_file\"")
             } else {
-                "exec gitfluff lint \"$1\"\n"
+                ("", "\"$1\"")
+            };
+            let flags = if write { " --write" } else { "" };
+            format!("{prelude}exec gitfluff lint {file}{flags}\n")
+        }
+        HookKind::PrePush => {
+            if write {
+                bail!(
+                    "--write is not supported for pre-push hooks (pushed commits are already recorded)"
+                );
+            }
+            if robust {
+                bail!("--robust only applies to commit-msg hooks");
             }
+            PRE_PUSH_SCRIPT.to_string()
+        }
+    };
+
+    let chained = if !chain {
+        String::new()
+    } else {
+        let local = format!("\"$(dirname \"$0\")/{}.local\"", hook_filename(kind));
+        match kind {
+            HookKind::CommitMsg => format!("{local} \"$@\" || exit $?\n"),
+            // The original hook consumes the ref list on stdin, so replay it for gitfluff.
+            HookKind::PrePush => format!(
+                "input=$(cat)\nprintf '%s\\n' \"$input\" | {local} \"$@\" || exit $?\nexec <<GITFLUFF_REFS\n$input\nGITFLUFF_REFS\n"
+            ),
         }
     };
 
-    Ok(format!("#!/bin/sh\n{}\n", base.trim_end()))
+    Ok(format!("#!/bin/sh\n{chained}{}\n", base.trim_end()))
 }
 
 fn apply_executable_permissions(path: &Path) -> Result<()> {
@@ -0,0 +1,35 @@
+//! Commit message linting, as used by the `gitfluff` CLI.
+//!
+//! Build [`LintOptions`] (directly, from a [`presets::Preset`], or from a loaded config file) and
+//! pass a message to [`lint_message`]:
+//!
+//! ```
+//! use gitfluff::{LintOptions, lint_message};
+//!
+//! let mut options = LintOptions::default();
+//! options.enforce_conventional_spec = true;
+//!
+//! let outcome = lint_message("feat: add login.", &options);
+//! assert_eq!(outcome.violations_before[0].id, "subject-full-stop");
+//!
+//! options.autofix = true;
+//! let fixed = lint_message("feat: add login.", &options);
+//! assert_eq!(fixed.cleaned_message, "feat: add login");
+//! assert!(fixed.violations_after.is_empty());
+//! ```
+
+pub mod commitlint;
+pub mod config;
+pub mod lint;
+pub mod presets;
+pub mod resolve;
+pub mod rule_docs;
+
+pub use config::{FileConfig, RulesConfig, load_config};
+pub use lint::{
+    LintOptions, LintOutcome, Severity, Violation, build_cleanup_rule, build_exclude_rule,
+    build_message_pattern, build_message_patterns, build_title_prefix_rule,
+    build_title_suffix_rule, lint_message,
+};
+pub use presets::{Preset, resolve_preset};
+pub use resolve::{CliOverrides, Report, resolve_options, validate};
@@ -1,80 +1,52 @@
+mod baseline;
 mod cli;
-mod config;
+mod diff;
+mod encoding;
+mod git;
 mod hooks;
-mod lint;
-mod presets;
 
+use std::collections::BTreeMap;
 use std::fs;
 use std::io::IsTerminal;
 use std::io::{self, Read, Write};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
 
 use anyhow::{Context, Result, anyhow};
 use clap::Parser;
 
-use crate::cli::{Cli, ColorMode, Commands, HookCommand, HookInstallArgs, LintArgs};
-use crate::config::load_config;
-use crate::hooks::install_hook;
-use crate::lint::{
-    BodyPolicy, LintOptions, build_cleanup_rule, build_exclude_rule, build_message_pattern,
-    build_title_prefix_rule, build_title_suffix_rule, lint_message,
+use crate::baseline::{Baseline, BaselineMode};
+use crate::cli::{
+    Cli, ColorMode, Commands, ConfigCommand, ExplainArgs, HookCommand, HookInstallArgs,
+    HookUninstallArgs, LintArgs, OutputFormat,
 };
-use crate::presets::resolve_preset;
-
-const AI_EXCLUDE_RULES: &[(&str, &str)] = &[
-    (
-        "(?mi)^Co-Authored-By:.*(?:Claude|Anthropic|ChatGPT|GPT|OpenAI).*$",
-        "Remove AI co-author attribution lines",
-    ),
-    (
-        "🤖 Generated with",
-        "Remove AI generation notices from commit messages",
-    ),
-];
-
-const AI_CLEANUP_RULES: &[(&str, &str, &str)] = &[
-    (
-        "(?ims)\\n?\\s*(?:🤖\\s*)?Generated with.*?(?:Co-Authored-By:.*(?:Claude|Anthropic).*(?:\\n\\s*<[^>\\n]+>)?)+\\s*",
-        "\n",
-        "Remove Claude Code attribution block",
-    ),
-    (
-        "(?m)^.*🤖 Generated with.*\n?",
-        "",
-        "Remove AI generation banner",
-    ),
-    (
-        "(?mi)^Generated with Claude.*\n?",
-        "",
-        "Remove plain Claude generation banner",
-    ),
-    (
-        "(?mi)^Co-Authored-By:.*(?:Claude|Anthropic).*\n?",
-        "",
-        "Drop Co-Authored-By lines referencing AI assistants",
-    ),
-    ("(?mi)^-\\s*Claude.*\n?", "", "Remove Claude bullet entries"),
-    (
-        "(?s)\\A\\s*\n+",
-        "",
-        "Trim leading blank lines introduced by cleanup",
-    ),
-    (
-        "(?s)\n\\s*\n\\z",
-        "\n",
-        "Trim trailing blank lines introduced by cleanup",
-    ),
-    ("\n{3,}", "\n\n", "Collapse excessive blank lines"),
-];
-
-const DEFAULT_TITLE_PREFIX_SEPARATOR: &str = " * ";
-const DEFAULT_TITLE_SUFFIX_SEPARATOR: &str = " ";
+use crate::diff::unified_diff;
+use crate::encoding::{TextEncoding, decode, encode};
+use crate::git::{RangeFilter, commits_in_range, head_commit};
+use crate::hooks::{hooks_dir, install_hook, uninstall_hook};
+use gitfluff::commitlint::import_commitlint;
+use gitfluff::config::{
+    DefaultSource, FileConfig, changed_files_start_dir, config_schema, load_config,
+};
+use gitfluff::lint::{
+    CleanupEdit, FIXABLE_RULES, LintOptions, Marker, RULE_IDS, RuleResult, RuleStatus, Severity,
+    Violation, cleanup_culprit, default_severity, is_merge_message, lint_message, suggest_fixes,
+    validate_rule_ids,
+};
+use gitfluff::resolve::{CliOverrides, resolve_options};
+use gitfluff::rule_docs::explain_rule;
 
 fn main() {
-    let exit_code = match run() {
+    let cli = Cli::parse();
+    // Fatal errors honor `--color` / `--no-color` too; only `lint` has those flags.
+    let color = match &cli.command {
+        Commands::Lint(args) => color_mode(args),
+        _ => ColorMode::Auto,
+    };
+    let exit_code = match run(cli) {
         Ok(code) => code,
         Err(err) => {
-            let mut reporter = Reporter::new(ColorMode::Auto);
+            let mut reporter = Reporter::new(color);
             let _ = reporter.error(format_error(&err));
             2
         }
@@ -83,205 +55,275 @@ fn main() {
     std::process::exit(exit_code);
 }
 
-fn run() -> Result<i32> {
-    let cli = Cli::parse();
-
+fn run(cli: Cli) -> Result<i32> {
     match cli.command {
-        Commands::Lint(args) => run_lint(*args),
+        Commands::Lint(args) => {
+            let exit_zero = args.exit_zero || args.fix_only;
+            let code = run_lint(*args)?;
+            Ok(if exit_zero { 0 } else { code })
+        }
         Commands::Hook(HookCommand::Install(args)) => run_hook_install(args),
+        Commands::Hook(HookCommand::Uninstall(args)) => run_hook_uninstall(args),
+        Commands::Explain(args) => run_explain(args),
+        Commands::Config(ConfigCommand::Schema) => run_config_schema(),
     }
 }
 
 fn run_hook_install(args: HookInstallArgs) -> Result<i32> {
-    let cwd = std::env::current_dir().context("failed to discover current directory")?;
-    let path = install_hook(&cwd, args.kind, args.write, args.force)?;
+    let cwd = base_dir(args.base_dir.as_deref())?;
+    let path = install_hook(
+        &cwd,
+        args.kind,
+        args.write,
+        args.force,
+        args.chain,
+        args.robust,
+    )?;
     println!(
         "gitfluff: info: Installed {} hook at {}",
         hook_label(args.kind),
         path.display()
     );
+    if hooks_dir(&cwd)?.from_hooks_path {
+        println!("gitfluff: info: Used the directory configured by core.hooksPath");
+    }
     Ok(0)
 }
 
-fn run_lint(args: LintArgs) -> Result<i32> {
-    let message_data = load_message(&args)?;
-    let cwd = std::env::current_dir().context("failed to discover current directory")?;
-
-    if is_merge_commit_in_progress(&cwd) {
-        return Ok(0);
-    }
-
-    let mut reporter = Reporter::new(args.color);
-    let loaded_config = load_config(args.config.as_deref(), &cwd)?;
-
-    let preset_name = args
-        .preset
-        .clone()
-        .or_else(|| {
-            loaded_config
-                .as_ref()
-                .and_then(|(_, cfg)| cfg.preset.clone())
-        })
-        .unwrap_or_else(|| "conventional".to_string());
-
-    let preset =
-        resolve_preset(&preset_name).ok_or_else(|| anyhow!("unknown preset `{}`", preset_name))?;
-
-    let mut enforce_spec = preset.enforce_spec;
-    let mut message_pattern = Some(build_message_pattern(
-        preset.message_pattern,
-        Some(preset.description.to_string()),
-    )?);
-
-    if let Some((_, cfg)) = &loaded_config
-        && let Some(rule) = &cfg.rules.message
-    {
-        message_pattern = Some(build_message_pattern(
-            &rule.pattern,
-            rule.description.clone(),
-        )?);
-        enforce_spec = false;
+fn run_hook_uninstall(args: HookUninstallArgs) -> Result<i32> {
+    let cwd = base_dir(args.base_dir.as_deref())?;
+    let (path, restored) = uninstall_hook(&cwd, args.kind, args.force)?;
+    println!(
+        "gitfluff: info: Removed {} hook at {}",
+        hook_label(args.kind),
+        path.display()
+    );
+    if restored {
+        println!(
+            "gitfluff: info: Restored the chained hook to {}",
+            path.display()
+        );
     }
+    Ok(0)
+}
 
-    if let Some(pattern) = &args.msg_pattern {
-        let desc = args
-            .msg_pattern_description
-            .clone()
-            .or_else(|| Some(format!("Commit message must match pattern `{pattern}`")));
-        message_pattern = Some(build_message_pattern(pattern, desc)?);
-        enforce_spec = false;
-    } else if args.msg_pattern_description.is_some()
-        && let Some(mp) = message_pattern.as_mut()
-    {
-        mp.description = args.msg_pattern_description.clone();
+/// Directory every discovery walk starts from: `--base-dir` when given, else the working
+/// directory.
+fn base_dir(flag: Option<&Path>) -> Result<PathBuf> {
+    match flag {
+        Some(dir) if dir.is_dir() => Ok(dir.to_path_buf()),
+        Some(dir) => Err(anyhow!("--base-dir {} is not a directory", dir.display())),
+        None => std::env::current_dir().context("failed to discover current directory"),
     }
+}
 
-    let mut options = LintOptions {
-        message_pattern,
-        body_policy: preset.body_policy,
-        enforce_conventional_spec: enforce_spec,
-        ..Default::default()
-    };
-
-    let mut body_policy = preset.body_policy;
-    let mut forbid_emojis = false;
-    let mut forbid_non_ascii = false;
-    let mut title_prefix_pattern: Option<String> = None;
-    let mut title_prefix_separator = DEFAULT_TITLE_PREFIX_SEPARATOR.to_string();
-    let mut title_suffix_pattern: Option<String> = None;
-    let mut title_suffix_separator = DEFAULT_TITLE_SUFFIX_SEPARATOR.to_string();
-
-    if let Some((_, cfg)) = &loaded_config {
-        let single_line_flag = cfg.rules.single_line.unwrap_or(false);
-        let require_body_flag = cfg.rules.require_body.unwrap_or(false);
-        forbid_emojis = cfg.rules.no_emojis.unwrap_or(false);
-        forbid_non_ascii = cfg.rules.ascii_only.unwrap_or(false);
-
-        if let Some(pattern) = &cfg.rules.title_prefix {
-            title_prefix_pattern = Some(pattern.clone());
-        }
-        if let Some(separator) = &cfg.rules.title_prefix_separator {
-            title_prefix_separator = separator.clone();
-        }
-        if let Some(pattern) = &cfg.rules.title_suffix {
-            title_suffix_pattern = Some(pattern.clone());
-        }
-        if let Some(separator) = &cfg.rules.title_suffix_separator {
-            title_suffix_separator = separator.clone();
-        }
-
-        if single_line_flag && require_body_flag {
-            return Err(anyhow!(
-                "configuration cannot enable both `single_line` and `require_body` rules"
-            ));
-        }
-
-        if single_line_flag {
-            body_policy = BodyPolicy::SingleLine;
-        } else if require_body_flag {
-            body_policy = BodyPolicy::RequireBody;
-        } else {
-            if matches!(cfg.rules.single_line, Some(false))
-                && matches!(body_policy, BodyPolicy::SingleLine)
-            {
-                body_policy = BodyPolicy::Any;
-            }
-            if matches!(cfg.rules.require_body, Some(false))
-                && matches!(body_policy, BodyPolicy::RequireBody)
-            {
-                body_policy = BodyPolicy::Any;
-            }
-        }
-
-        for exclude in &cfg.rules.excludes {
-            options.exclude_rules.push(build_exclude_rule(
-                &exclude.pattern,
-                exclude.message.clone(),
-            )?);
-        }
-
-        for cleanup in &cfg.rules.cleanup {
-            options.cleanup_rules.push(build_cleanup_rule(
-                &cleanup.find,
-                &cleanup.replace,
-                cleanup.description.clone(),
-            )?);
-        }
-    }
+fn run_explain(args: ExplainArgs) -> Result<i32> {
+    print!("{}", explain_rule(&args.rule)?);
+    Ok(0)
+}
 
-    for exclude in &args.exclude {
-        let (pattern, message) = parse_exclude_arg(exclude)?;
-        options
-            .exclude_rules
-            .push(build_exclude_rule(&pattern, message)?);
-    }
+fn run_config_schema() -> Result<i32> {
+    let schema = serde_json::to_string_pretty(&config_schema())
+        .context("failed to serialize config schema")?;
+    println!("{schema}");
+    Ok(0)
+}
 
-    for cleanup in &args.cleanup {
-        let (find, replace) = parse_cleanup_arg(cleanup)?;
-        options
-            .cleanup_rules
-            .push(build_cleanup_rule(&find, &replace, None)?);
+/// The `lint` flags that override the config and preset.
+fn lint_overrides(args: &LintArgs) -> Result<CliOverrides> {
+    let mut cleanup = Vec::new();
+    for raw in &args.cleanup {
+        let (find, replace) = parse_cleanup_arg(raw)?;
+        cleanup.push((find, replace, None));
     }
-
     if let Some(pattern) = &args.cleanup_pattern {
-        let replace = args.cleanup_replacement.clone().unwrap_or_default();
-        options.cleanup_rules.push(build_cleanup_rule(
-            pattern,
-            &replace,
+        cleanup.push((
+            pattern.clone(),
+            args.cleanup_replacement.clone().unwrap_or_default(),
             args.cleanup_description.clone(),
-        )?);
+        ));
     }
+    Ok(CliOverrides {
+        preset: args.preset.clone(),
+        fallback_preset: env_setting("GITFLUFF_PRESET"),
+        msg_pattern: args.msg_pattern.clone(),
+        msg_pattern_description: args.msg_pattern_description.clone(),
+        exclude: args
+            .exclude
+            .iter()
+            .map(|raw| parse_exclude_arg(raw))
+            .collect::<Result<_>>()?,
+        cleanup,
+        no_emojis: args.no_emojis,
+        ascii_only: args.ascii_only,
+        title_prefix: args
+            .title_prefix
+            .clone()
+            .map(|pattern| (pattern, args.title_prefix_separator.clone())),
+        title_suffix: args
+            .title_suffix
+            .clone()
+            .map(|pattern| (pattern, args.title_suffix_separator.clone())),
+        subject_lowercase_first: args.subject_lowercase_first,
+        breaking_requires_footer: args.breaking_requires_footer,
+        single_line: args.single_line,
+        require_body: args.require_body,
+        write: args.write || args.dry_run || args.fix_only,
+        autofix_only: args.autofix_only.clone(),
+        body_wrap: args.body_wrap,
+        body_max_lines: args.body_max_lines,
+        subject_max_length: args.subject_max_length,
+        subject_case_scope: args.subject_case_scope,
+        length_unit: args.length_unit,
+        line_ending: args.line_ending,
+    })
+}
 
-    if args.single_line {
-        body_policy = BodyPolicy::SingleLine;
-    } else if args.require_body {
-        body_policy = BodyPolicy::RequireBody;
+/// `--no-color` is shorthand for `--color never`.
+fn color_mode(args: &LintArgs) -> ColorMode {
+    if args.no_color {
+        ColorMode::Never
+    } else {
+        args.color
     }
+}
 
-    if args.no_emojis {
-        forbid_emojis = true;
+fn run_lint(mut args: LintArgs) -> Result<i32> {
+    if args.porcelain {
+        args.format = OutputFormat::Porcelain;
     }
-    if args.ascii_only {
-        forbid_non_ascii = true;
+    let batch = args.range.is_some() || args.messages_file.is_some();
+    let cwd = base_dir(args.base_dir.as_deref())?;
+
+    let mut reporter = Reporter::new(color_mode(&args)).with_format(args.format);
+    reporter.context = args.input_name.clone();
+    reporter.verbose = args.verbose || env_flag("GITFLUFF_VERBOSE");
+    reporter.timing = args.timing;
+    let config_path = args
+        .config
+        .clone()
+        .or_else(|| env_setting("GITFLUFF_CONFIG").map(PathBuf::from));
+    if args.list_rules {
+        // Same config and flags as a real lint, so severities and disabled rules match it.
+        let mut config = if args.no_config {
+            None
+        } else {
+            load_config(
+                config_path.as_deref(),
+                &cwd,
+                current_branch(&cwd).as_deref(),
+            )?
+        };
+        if let Some((path, cfg)) = &mut config
+            && cfg.rules.import_commitlint.unwrap_or(false)
+        {
+            let dir = path.parent().unwrap_or_else(|| Path::new("."));
+            import_commitlint(dir, &mut cfg.rules)?;
+        }
+        let cfg = config.as_ref().map(|(_, cfg)| cfg);
+        let options = resolve_options(&lint_overrides(&args)?, cfg, &cwd)?;
+        let severities = cfg
+            .map(|cfg| cfg.rules.severity.clone())
+            .unwrap_or_default();
+        reporter.report(&list_rules(&options, &severities).to_string())?;
+        return Ok(0);
     }
-    if let Some(pattern) = &args.title_prefix {
-        title_prefix_pattern = Some(pattern.clone());
-        title_prefix_separator = args.title_prefix_separator.clone();
+    if skip_requested() {
+        reporter.quiet = args.quiet;
+        reporter.info("skipping lint because GITFLUFF_SKIP is set")?;
+        // Only consulted for `write = true`; a broken config must not defeat the escape hatch.
+        let config = if args.no_config || !(args.stdin || args.message.is_some()) {
+            None
+        } else {
+            load_config(config_path.as_deref(), &cwd, None)
+                .ok()
+                .flatten()
+        };
+        echo_skipped_message(&args, config.as_ref().map(|(_, cfg)| cfg))?;
+        return Ok(0);
     }
-    if let Some(pattern) = &args.title_suffix {
-        title_suffix_pattern = Some(pattern.clone());
-        title_suffix_separator = args.title_suffix_separator.clone();
+    if args.last {
+        if find_git_dir(&cwd).is_none() {
+            return Err(anyhow!("--last requires a git repository"));
+        }
+        let commit = head_commit(&cwd).context("--last found no commit to lint")?;
+        args.message = Some(commit.message);
     }
 
-    let write_requested = if args.write {
-        true
-    } else if let Some((_, cfg)) = &loaded_config {
-        cfg.write.unwrap_or(false)
+    let config_start = match &args.changed_files_from {
+        Some(list) => {
+            let changed = read_changed_files(list, args.stdin)?;
+            changed_files_start_dir(&cwd, &changed).unwrap_or_else(|| cwd.clone())
+        }
+        None => cwd.clone(),
+    };
+    // The config is loaded before the message source is checked because `default_source` can
+    // supply one; without a source, though, the usage error wins over a broken config.
+    let mut loaded_config = if args.no_config {
+        None
     } else {
-        false
+        match load_config(
+            config_path.as_deref(),
+            &config_start,
+            current_branch(&cwd).as_deref(),
+        ) {
+            Ok(config) => config,
+            Err(err) => {
+                if !batch {
+                    ensure_message_source(&args)?;
+                }
+                return Err(err);
+            }
+        }
     };
+    let lint_merges = args.lint_merges
+        || loaded_config
+            .as_ref()
+            .and_then(|(_, cfg)| cfg.rules.lint_merges)
+            .unwrap_or(false);
+    // Checked before anything else touches the message so a pending merge costs one file read.
+    if !batch && let Some(merge_heads) = pending_merge_heads(&cwd) {
+        let skip_octopus = args.skip_octopus_merges
+            || loaded_config
+                .as_ref()
+                .and_then(|(_, cfg)| cfg.rules.skip_octopus_merges)
+                .unwrap_or(false);
+        // MERGE_HEAD lists every merged head; together with HEAD that is the parent count.
+        if !lint_merges || (skip_octopus && merge_heads + 1 > 2) {
+            echo_skipped_message(&args, loaded_config.as_ref().map(|(_, cfg)| cfg))?;
+            return Ok(0);
+        }
+    }
+    if let Some((path, cfg)) = &mut loaded_config
+        && cfg.rules.import_commitlint.unwrap_or(false)
+    {
+        let dir = path.parent().unwrap_or_else(|| Path::new("."));
+        import_commitlint(dir, &mut cfg.rules)?;
+    }
+    if !batch && !has_message_source(&args) {
+        let default_source = loaded_config
+            .as_ref()
+            .and_then(|(_, cfg)| cfg.rules.default_source)
+            .unwrap_or_default();
+        if default_source == DefaultSource::CommitEditmsg {
+            let git_dir = find_git_dir(&cwd).ok_or_else(|| {
+                anyhow!("default_source = \"commit-editmsg\" but no git directory was found")
+            })?;
+            args.commit_file = Some(git_dir.join("COMMIT_EDITMSG"));
+        }
+        ensure_message_source(&args)?;
+    }
+
+    reporter.quiet = args.quiet
+        || loaded_config
+            .as_ref()
+            .and_then(|(_, cfg)| cfg.rules.quiet)
+            .unwrap_or(false);
 
-    options.autofix = write_requested;
+    let dry_run = args.dry_run;
+    let overrides = lint_overrides(&args)?;
+    let options = resolve_options(&overrides, loaded_config.as_ref().map(|(_, cfg)| cfg), &cwd)?;
+    let write_requested = options.autofix;
 
     let exit_nonzero_on_rewrite = if args.exit_nonzero_on_rewrite {
         true
@@ -291,39 +333,120 @@ fn run_lint(args: LintArgs) -> Result<i32> {
         false
     };
 
-    options.body_policy = body_policy;
-    options.forbid_emojis = forbid_emojis;
-    options.forbid_non_ascii = forbid_non_ascii;
-
-    if let Some(pattern) = title_prefix_pattern.as_ref() {
-        options.title_prefix = Some(build_title_prefix_rule(pattern, &title_prefix_separator)?);
+    if let Some(range) = &args.range {
+        let severities = loaded_config.as_ref().map(|(_, cfg)| &cfg.rules.severity);
+        let baseline = if let Some(path) = &args.write_baseline {
+            BaselineMode::Write(path.clone())
+        } else if let Some(path) = &args.baseline {
+            BaselineMode::Apply(Baseline::load(path)?)
+        } else {
+            BaselineMode::Off
+        };
+        let rules = loaded_config.as_ref().map(|(_, cfg)| &cfg.rules);
+        let filter = RangeFilter {
+            include_merges: lint_merges,
+            skip_authors: rules
+                .map(|rules| rules.range_skip_authors.clone())
+                .unwrap_or_default(),
+            skip_pattern: rules
+                .and_then(|rules| rules.range_skip_pattern.as_deref())
+                .map(regex::Regex::new)
+                .transpose()
+                .context("invalid range_skip_pattern")?,
+        };
+        return lint_range(
+            &cwd,
+            range,
+            &filter,
+            &options,
+            severities,
+            baseline,
+            &mut reporter,
+        );
     }
-
-    if let Some(pattern) = title_suffix_pattern.as_ref() {
-        options.title_suffix = Some(build_title_suffix_rule(pattern, &title_suffix_separator)?);
+    if let Some(path) = &args.messages_file {
+        let severities = loaded_config.as_ref().map(|(_, cfg)| &cfg.rules.severity);
+        return lint_messages_file(
+            path,
+            &args.delimiter,
+            args.encoding.as_deref(),
+            &options,
+            severities,
+            &mut reporter,
+        );
     }
 
-    for (pattern, message) in AI_EXCLUDE_RULES {
-        options
-            .exclude_rules
-            .push(build_exclude_rule(pattern, Some((*message).to_string()))?);
+    let message_data = load_message(&args)?;
+    reporter.stdout_is_content = write_requested
+        && !dry_run
+        && matches!(
+            message_data.source,
+            MessageSource::Stdin | MessageSource::Literal
+        );
+    let allow_merge_commits = loaded_config
+        .as_ref()
+        .and_then(|(_, cfg)| cfg.rules.allow_merge_commits)
+        .unwrap_or(true);
+    // CI lints merge messages long after MERGE_HEAD is gone, so recognise them by their header too.
+    if allow_merge_commits && !lint_merges && is_merge_message(&message_data.text) {
+        reporter.info("skipping merge commit message")?;
+        if reporter.stdout_is_content {
+            apply_write(&message_data, &message_data.text)?;
+        }
+        return Ok(0);
     }
-
-    for (find, replace, desc) in AI_CLEANUP_RULES {
-        options.cleanup_rules.push(build_cleanup_rule(
-            find,
-            replace,
-            Some((*desc).to_string()),
-        )?);
+    let mut outcome = lint_message(&message_data.text, &options);
+    if let Some((_, cfg)) = &loaded_config {
+        outcome.apply_severities(&cfg.rules.severity);
+    }
+    reporter.detail(format!(
+        "original message:\n{}",
+        message_data.text.trim_end_matches('\n')
+    ))?;
+    for summary in &outcome.cleanup_summaries {
+        reporter.detail(format!("step: {summary}"))?;
     }
+    if outcome.cleanup_summaries.is_empty() {
+        reporter.detail("no cleanup or autofix step changed the message")?;
+    } else {
+        reporter.detail(format!(
+            "cleaned message:\n{}",
+            outcome.cleaned_message.trim_end_matches('\n')
+        ))?;
+    }
+    let suggestions = if args.suggest {
+        suggest_fixes(&message_data.text, &options)
+    } else {
+        Vec::new()
+    };
 
-    let outcome = lint_message(&message_data.text, &options);
+    if outcome.suppressed_rules.iter().any(|rule| rule == "all") {
+        reporter.info("all rules suppressed by gitfluff-disable trailer")?;
+    } else if !outcome.suppressed_rules.is_empty() {
+        reporter.info(format!(
+            "rules suppressed by gitfluff-disable trailer: {}",
+            outcome.suppressed_rules.join(", ")
+        ))?;
+    }
+    if let Err(err) = validate_rule_ids(
+        outcome
+            .suppressed_rules
+            .iter()
+            .filter(|rule| rule.as_str() != "all"),
+    ) {
+        reporter.warn(format!("gitfluff-disable trailer: {err}"))?;
+    }
 
     if outcome.cleanup_summaries.is_empty() {
         // nothing to do
     } else if write_requested {
+        let verb = if dry_run {
+            "would apply cleanup"
+        } else {
+            "applied cleanup"
+        };
         for summary in &outcome.cleanup_summaries {
-            reporter.info(format!("applied cleanup: {summary}"))?;
+            reporter.info(format!("{verb}: {summary}"))?;
         }
     } else {
         for summary in &outcome.cleanup_summaries {
@@ -331,39 +454,172 @@ fn run_lint(args: LintArgs) -> Result<i32> {
         }
     }
 
+    if args.show_cleanup_diff {
+        for change in &outcome.cleanup_changes {
+            match &change.origin {
+                Some(origin) => {
+                    reporter.info(format!("cleanup diff: {} (from {origin})", change.summary))?
+                }
+                None => reporter.info(format!("cleanup diff: {}", change.summary))?,
+            }
+            for edit in &change.edits {
+                reporter.diff(&render_cleanup_edit(edit))?;
+            }
+        }
+    }
+
+    let (active_message, active_markers) = if write_requested {
+        (outcome.cleaned_message.as_str(), &outcome.markers_after)
+    } else {
+        (message_data.text.as_str(), &outcome.markers_before)
+    };
+
+    let cap = args.max_violations;
+    let shown = cap.unwrap_or(usize::MAX);
     let active_violations = if write_requested {
+        // Matched by rule id: a fix can change a message's wording (a shorter header reports a
+        // new length) without resolving it.
+        let fires = |list: &[Violation], violation: &Violation| {
+            list.iter().any(|other| other.id == violation.id)
+        };
         for fixed in outcome
             .violations_before
             .iter()
-            .filter(|msg| !outcome.violations_after.contains(msg))
+            .filter(|violation| !fires(&outcome.violations_after, violation))
         {
-            reporter.info(format!("fixed: {fixed}"))?;
+            if dry_run {
+                reporter.info(format!("would fix: {fixed}"))?;
+            } else {
+                reporter.info(format!("fixed: {fixed}"))?;
+            }
         }
 
-        for warning in &outcome.warnings_after {
-            reporter.warn(warning)?;
+        for introduced in outcome
+            .violations_after
+            .iter()
+            .filter(|violation| !fires(&outcome.violations_before, violation))
+        {
+            match cleanup_culprit(&message_data.text, &options, introduced) {
+                Some(rule) => reporter.warn(format!(
+                    "cleanup rule introduced new violation: {introduced} (likely culprit: {rule})"
+                ))?,
+                None => reporter.warn(format!("cleanup introduced new violation: {introduced}"))?,
+            }
         }
 
-        for violation in &outcome.violations_after {
-            reporter.error(violation)?;
+        for warning in outcome.warnings_after.iter().take(shown) {
+            reporter.warning(warning)?;
+        }
+        reporter.truncated(outcome.warnings_after.len(), cap, Level::Warning)?;
+
+        for violation in outcome.violations_after.iter().take(shown) {
+            if args.fix_only {
+                // Enforcement is left to a later check; only note what cleanup could not fix.
+                reporter.info(format!(
+                    "not fixed [{}]: {}",
+                    violation.id,
+                    with_origin(violation)
+                ))?;
+            } else {
+                reporter.violation(violation)?;
+            }
         }
+        let level = if args.fix_only {
+            Level::Info
+        } else {
+            Level::Error
+        };
+        reporter.truncated(outcome.violations_after.len(), cap, level)?;
 
         &outcome.violations_after
     } else {
-        for warning in &outcome.warnings_before {
-            reporter.warn(warning)?;
+        for warning in outcome.warnings_before.iter().take(shown) {
+            reporter.warning(warning)?;
         }
+        reporter.truncated(outcome.warnings_before.len(), cap, Level::Warning)?;
 
-        for violation in &outcome.violations_before {
-            reporter.error(violation)?;
+        for violation in outcome.violations_before.iter().take(shown) {
+            reporter.violation(violation)?;
         }
+        reporter.truncated(outcome.violations_before.len(), cap, Level::Error)?;
 
         &outcome.violations_before
     };
 
+    for suggestion in &suggestions {
+        reporter.suggest(format!("{}: {}", suggestion.violation, suggestion.fix))?;
+    }
+
+    let active_warnings = if write_requested {
+        &outcome.warnings_after
+    } else {
+        &outcome.warnings_before
+    };
+    reporter.flush_groups()?;
+    if args.explain_failure && (!active_violations.is_empty() || !active_warnings.is_empty()) {
+        reporter.raw(&render_explanation(
+            active_message,
+            active_markers,
+            active_violations,
+            active_warnings,
+        ))?;
+    }
+
+    let rule_results = if args.show_rules {
+        let no_overrides = BTreeMap::new();
+        let severities = loaded_config
+            .as_ref()
+            .map_or(&no_overrides, |(_, cfg)| &cfg.rules.severity);
+        outcome.rule_results(&options, severities, write_requested)
+    } else {
+        Vec::new()
+    };
+    if args.format.is_human() {
+        reporter.raw(&render_rule_results(&rule_results))?;
+    }
+
+    if !args.fix_only {
+        reporter.summary(active_violations.len(), active_warnings.len())?;
+    }
+
     let did_rewrite = write_requested && outcome.cleaned_message != message_data.text;
 
-    if write_requested {
+    if args.format == OutputFormat::Json {
+        let mut document = serde_json::json!({
+            "violations": active_violations,
+            "warnings": active_warnings,
+            "cleanups": outcome.cleanup_summaries,
+            "suggestions": suggestions,
+            "rewritten": did_rewrite,
+        });
+        if args.show_rules {
+            document["rules"] = serde_json::json!(rule_results);
+        }
+        reporter.report(&document.to_string())?;
+    } else if args.format == OutputFormat::Porcelain {
+        reporter.report(&format!("rewritten\t{did_rewrite}"))?;
+    } else if args.format == OutputFormat::Sarif {
+        let uri = match &message_data.source {
+            MessageSource::File(path) => path
+                .strip_prefix(&cwd)
+                .unwrap_or(path)
+                .display()
+                .to_string(),
+            other => other.label(),
+        };
+        let document = sarif_document(active_violations, active_warnings, &uri);
+        reporter.report(&document.to_string())?;
+    }
+
+    if dry_run {
+        if did_rewrite {
+            reporter.diff(&unified_diff(
+                &message_data.text,
+                &outcome.cleaned_message,
+                &message_data.source.label(),
+            ))?;
+        }
+    } else if write_requested {
         apply_write(&message_data, &outcome.cleaned_message)?;
     } else if message_data.source == MessageSource::Literal && !active_violations.is_empty() {
         // no-op, keep behavior simple
@@ -371,8 +627,13 @@ fn run_lint(args: LintArgs) -> Result<i32> {
 
     if active_violations.is_empty() {
         if did_rewrite && exit_nonzero_on_rewrite {
-            reporter
-                .info("commit message was rewritten; please re-run the commit to review changes")?;
+            if dry_run {
+                reporter.info("commit message would be rewritten (dry run, no changes written)")?;
+            } else {
+                reporter.info(
+                    "commit message was rewritten; please re-run the commit to review changes",
+                )?;
+            }
             Ok(1)
         } else {
             Ok(0)
@@ -382,11 +643,121 @@ fn run_lint(args: LintArgs) -> Result<i32> {
     }
 }
 
+/// A SARIF 2.1.0 log with one run; every result points at the message source, which has no
+/// finer location a code-scanning tool could use. `uri` is written as a relative URI reference.
+fn sarif_document(
+    violations: &[Violation],
+    warnings: &[Violation],
+    uri: &str,
+) -> serde_json::Value {
+    let uri = uri_reference(uri);
+    let result = |finding: &Violation, level: &str| {
+        serde_json::json!({
+            "ruleId": finding.id,
+            "level": level,
+            "message": { "text": with_origin(finding) },
+            "locations": [{
+                "physicalLocation": { "artifactLocation": { "uri": uri } }
+            }],
+        })
+    };
+    let results: Vec<_> = violations
+        .iter()
+        .map(|violation| result(violation, "error"))
+        .chain(warnings.iter().map(|warning| result(warning, "warning")))
+        .collect();
+    let rules: Vec<_> = RULE_IDS
+        .iter()
+        .map(|id| serde_json::json!({ "id": id }))
+        .collect();
+    serde_json::json!({
+        "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "gitfluff",
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "informationUri": env!("CARGO_PKG_REPOSITORY"),
+                    "rules": rules,
+                }
+            },
+            "results": results,
+        }],
+    })
+}
+
+/// `text` (a path, `stdin`, or a revision range) as a URI reference: `\` separators become `/` and
+/// every byte outside the unreserved set is percent-encoded, so spaces or `#` stay part of it.
+fn uri_reference(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for byte in text.replace('\\', "/").bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'.' | b'_' | b'~' | b'/') {
+            out.push(char::from(byte));
+        } else {
+            out.push_str(&format!("%{byte:02X}"));
+        }
+    }
+    out
+}
+
+/// `--list-rules`: every rule id with the severity it gets under `options` and the config's
+/// `[rules.severity]` overrides.
+fn list_rules(options: &LintOptions, severities: &BTreeMap<String, Severity>) -> serde_json::Value {
+    RULE_IDS
+        .iter()
+        .map(|&id| {
+            let level = match severities.get(id) {
+                Some(&level) if default_severity(id, options) != Severity::Off => level,
+                _ => default_severity(id, options),
+            };
+            let severity = match level {
+                Severity::Error => "error",
+                Severity::Warn => "warning",
+                Severity::Off => "off",
+            };
+            serde_json::json!({
+                "id": id,
+                "severity": severity,
+                "fixable": FIXABLE_RULES.iter().any(|&(rule, _)| rule == id),
+            })
+        })
+        .collect()
+}
+
+/// One `✓ id` / `✗ id` / `! id (warning)` / `- id (disabled)` line per rule.
+fn render_rule_results(results: &[RuleResult]) -> String {
+    let mut out = String::new();
+    for result in results {
+        let line = match result.status {
+            RuleStatus::Passed => format!("✓ {}", result.id),
+            RuleStatus::Failed => format!("✗ {}", result.id),
+            RuleStatus::Warned => format!("! {} (warning)", result.id),
+            RuleStatus::Skipped => format!("- {} (disabled)", result.id),
+        };
+        out.push_str(&line);
+        out.push('\n');
+    }
+    out
+}
+
+/// Render a cleanup edit as `-`/`+` lines: the matched text, then what replaced it.
+fn render_cleanup_edit(edit: &CleanupEdit) -> String {
+    let mut out = String::new();
+    for line in edit.removed.lines() {
+        out.push_str(&format!("-{line}\n"));
+    }
+    for line in edit.inserted.lines() {
+        out.push_str(&format!("+{line}\n"));
+    }
+    out
+}
+
 fn apply_write(message: &MessageData, cleaned: &str) -> Result<()> {
     match &message.source {
         MessageSource::File(path) => {
             if cleaned != message.text {
-                fs::write(path, cleaned).with_context(|| {
+                fs::write(path, encode(cleaned, message.encoding)?).with_context(|| {
                     format!(
                         "failed to write cleaned commit message to {}",
                         path.display()
@@ -395,49 +766,324 @@ fn apply_write(message: &MessageData, cleaned: &str) -> Result<()> {
             }
         }
         MessageSource::Stdin | MessageSource::Literal => {
+            let cleaned = with_trailing_newline_of(cleaned, &message.text);
             let mut stdout = io::stdout().lock();
             stdout
-                .write_all(cleaned.as_bytes())
+                .write_all(&encode(&cleaned, message.encoding)?)
                 .context("failed to write cleaned message to stdout")?;
         }
     }
     Ok(())
 }
 
-fn load_message(args: &LintArgs) -> Result<MessageData> {
-    if args.from_file.is_none()
-        && args.commit_file.is_none()
-        && !args.stdin
-        && args.message.is_none()
-    {
+/// `cleaned` ending with a line break exactly when `original` does, so piping a message through
+/// `--stdin --write` never adds or drops the final newline.
+fn with_trailing_newline_of(cleaned: &str, original: &str) -> String {
+    if cleaned == original {
+        return cleaned.to_string();
+    }
+    let body = cleaned.trim_end_matches(['\r', '\n']);
+    if original.ends_with("\r\n") {
+        format!("{body}\r\n")
+    } else if original.ends_with('\n') {
+        format!("{body}\n")
+    } else {
+        body.to_string()
+    }
+}
+
+/// Lint each commit in `range`, prefixing findings with the short sha. A baseline either hides
+/// each commit's grandfathered rule ids or, when being written, records them and never fails.
+fn lint_range(
+    repo_dir: &std::path::Path,
+    range: &str,
+    filter: &RangeFilter,
+    options: &LintOptions,
+    severities: Option<&BTreeMap<String, Severity>>,
+    baseline: BaselineMode,
+    reporter: &mut Reporter,
+) -> Result<i32> {
+    let started = Instant::now();
+    let commits = commits_in_range(repo_dir, range, filter.include_merges)?;
+    let git_elapsed = started.elapsed();
+    let mut failed = 0;
+    let mut skipped = 0;
+    let mut recorded = Baseline::default();
+    let mut batch = BatchReport::default();
+
+    for commit in &commits {
+        if let Some(reason) = filter.skip_reason(commit) {
+            reporter.info(format!("{}: skipped ({reason})", commit.short_sha()))?;
+            skipped += 1;
+            continue;
+        }
+        let grandfathered = match &baseline {
+            BaselineMode::Apply(baseline) => baseline.grandfathered(&commit.sha),
+            BaselineMode::Off | BaselineMode::Write(_) => None,
+        };
+        let failing = lint_labeled(
+            commit.short_sha(),
+            &commit.message,
+            options,
+            severities,
+            grandfathered,
+            reporter,
+            &mut batch,
+        )?;
+        recorded.record(&commit.sha, &failing);
+        if !failing.is_empty() {
+            failed += 1;
+        }
+    }
+
+    let skipped_note = if skipped > 0 {
+        format!(", {skipped} skipped")
+    } else {
+        String::new()
+    };
+    reporter.info(format!(
+        "{failed} of {} commit(s) in {range} failed{skipped_note}",
+        commits.len()
+    ))?;
+    reporter.timing_summary(
+        commits.len() - skipped,
+        started.elapsed(),
+        Some(git_elapsed),
+    )?;
+    batch.print(reporter, range)?;
+    if let BaselineMode::Write(path) = &baseline {
+        recorded.save(path)?;
+        reporter.info(format!(
+            "wrote baseline for {} commit(s) to {}",
+            recorded.len(),
+            path.display()
+        ))?;
+        return Ok(0);
+    }
+    Ok(if failed == 0 { 0 } else { 1 })
+}
+
+/// Lint every record of a batch file, split on `delimiter` (`nul`, `newline`, or a literal
+/// separator), reporting pass/fail per record.
+fn lint_messages_file(
+    path: &std::path::Path,
+    delimiter: &str,
+    encoding: Option<&str>,
+    options: &LintOptions,
+    severities: Option<&BTreeMap<String, Severity>>,
+    reporter: &mut Reporter,
+) -> Result<i32> {
+    let started = Instant::now();
+    let bytes = fs::read(path)
+        .with_context(|| format!("failed to read messages file {}", path.display()))?;
+    let (content, _) = decode(&bytes, encoding)
+        .map_err(|err| anyhow!("messages file {} {err}", path.display()))?;
+    let separator = match delimiter {
+        "nul" => "\0",
+        "newline" => "\n",
+        other => other,
+    };
+    let records: Vec<&str> = content
+        .split(separator)
+        .filter(|record| !record.trim().is_empty())
+        .collect();
+
+    let mut failed = 0;
+    let mut batch = BatchReport::default();
+    for (idx, record) in records.iter().enumerate() {
+        let label = format!("record {}", idx + 1);
+        let text = record.trim_start_matches('\n');
+        if lint_labeled(
+            &label, text, options, severities, None, reporter, &mut batch,
+        )?
+        .is_empty()
+        {
+            reporter.info(format!("{label}: pass"))?;
+        } else {
+            reporter.info(format!("{label}: fail"))?;
+            failed += 1;
+        }
+    }
+
+    reporter.info(format!(
+        "{failed} of {} message(s) in {} failed",
+        records.len(),
+        path.display()
+    ))?;
+    reporter.timing_summary(records.len(), started.elapsed(), None)?;
+    batch.print(reporter, &path.display().to_string())?;
+    Ok(if failed == 0 { 0 } else { 1 })
+}
+
+/// Findings of every message in a batch, kept for the single document `--format json` and
+/// `--format sarif` print once the batch is done.
+#[derive(Default)]
+struct BatchReport {
+    /// One `{"label", "violations", "warnings"}` object per linted message.
+    messages: Vec<serde_json::Value>,
+    /// Findings of all messages, each prefixed with its message's label, for SARIF.
+    violations: Vec<Violation>,
+    warnings: Vec<Violation>,
+}
+
+impl BatchReport {
+    fn record(&mut self, label: &str, violations: &[Violation], warnings: &[Violation]) {
+        self.messages.push(serde_json::json!({
+            "label": label,
+            "violations": violations,
+            "warnings": warnings,
+        }));
+        self.violations
+            .extend(violations.iter().map(|finding| labeled(label, finding)));
+        self.warnings
+            .extend(warnings.iter().map(|finding| labeled(label, finding)));
+    }
+
+    /// Print the batch document for machine-readable formats; the others already reported each
+    /// finding as it was found. `uri` names what was linted (the range or the messages file).
+    fn print(&self, reporter: &mut Reporter, uri: &str) -> Result<()> {
+        match reporter.format {
+            OutputFormat::Json => {
+                let document = serde_json::json!({ "messages": self.messages });
+                reporter.report(&document.to_string())?;
+            }
+            OutputFormat::Sarif => {
+                let document = sarif_document(&self.violations, &self.warnings, uri);
+                reporter.report(&document.to_string())?;
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+}
+
+/// `finding` with its message prefixed by the batch `label` of the message it came from.
+fn labeled(label: &str, finding: &Violation) -> Violation {
+    Violation {
+        id: finding.id,
+        message: format!("{label}: {}", finding.message),
+        origin: finding.origin.clone(),
+        fixable: finding.fixable,
+    }
+}
+
+/// Lint one message of a batch, prefixing each finding with `label` and skipping `grandfathered`
+/// rule ids. Returns the ids of the violations it reported; empty means the message passed.
+fn lint_labeled(
+    label: &str,
+    text: &str,
+    options: &LintOptions,
+    severities: Option<&BTreeMap<String, Severity>>,
+    grandfathered: Option<&std::collections::BTreeSet<String>>,
+    reporter: &mut Reporter,
+    batch: &mut BatchReport,
+) -> Result<Vec<&'static str>> {
+    let mut outcome = lint_message(text, options);
+    if let Some(severities) = severities {
+        outcome.apply_severities(severities);
+    }
+    if let Some(grandfathered) = grandfathered {
+        let is_new = |finding: &Violation| !grandfathered.contains(finding.id);
+        outcome.violations_before.retain(is_new);
+        outcome.warnings_before.retain(is_new);
+    }
+    for warning in &outcome.warnings_before {
+        reporter.warning(&labeled(label, warning))?;
+    }
+    for violation in &outcome.violations_before {
+        reporter.violation(&labeled(label, violation))?;
+    }
+    reporter.flush_groups()?;
+    batch.record(label, &outcome.violations_before, &outcome.warnings_before);
+    Ok(outcome.violations_before.iter().map(|v| v.id).collect())
+}
+
+/// When stdout carries the message (`--stdin --write`, `--message --write`), pass it through
+/// unchanged on a skipped lint so the next command in the pipe never gets an empty message.
+fn echo_skipped_message(args: &LintArgs, config: Option<&FileConfig>) -> Result<()> {
+    let write = args.write || args.fix_only || config.and_then(|cfg| cfg.write).unwrap_or(false);
+    if write && !args.dry_run && (args.stdin || args.message.is_some()) {
+        let message_data = load_message(args)?;
+        apply_write(&message_data, &message_data.text)?;
+    }
+    Ok(())
+}
+
+fn has_message_source(args: &LintArgs) -> bool {
+    args.from_file.is_some() || args.commit_file.is_some() || args.stdin || args.message.is_some()
+}
+
+fn ensure_message_source(args: &LintArgs) -> Result<()> {
+    if !has_message_source(args) {
         return Err(anyhow!(
-            "no commit message source provided (pass COMMIT_FILE, --from-file, --stdin, or --message)"
+            "no commit message source provided (pass COMMIT_FILE, --from-file, --stdin, --message, --last, --range, or --messages-file)"
         ));
     }
+    Ok(())
+}
 
-    let (text, source) = if let Some(path) = &args.from_file {
-        let content = fs::read_to_string(path)
-            .with_context(|| format!("failed to read commit message from {}", path.display()))?;
-        (content, MessageSource::File(path.clone()))
-    } else if let Some(path) = &args.commit_file {
-        let content = fs::read_to_string(path)
+fn load_message(args: &LintArgs) -> Result<MessageData> {
+    ensure_message_source(args)?;
+
+    let (bytes, source) = if let Some(path) = args.from_file.as_ref().or(args.commit_file.as_ref())
+    {
+        let content = fs::read(path)
             .with_context(|| format!("failed to read commit message from {}", path.display()))?;
         (content, MessageSource::File(path.clone()))
     } else if args.stdin {
-        let mut buf = String::new();
+        let mut buf = Vec::new();
         io::stdin()
-            .read_to_string(&mut buf)
+            .read_to_end(&mut buf)
             .context("failed to read commit message from stdin")?;
         (buf, MessageSource::Stdin)
     } else if let Some(message) = &args.message {
-        (message.clone(), MessageSource::Literal)
+        return Ok(MessageData {
+            text: message.clone(),
+            source: MessageSource::Literal,
+            encoding: TextEncoding::default(),
+        });
     } else {
         return Err(anyhow!(
             "no commit message source provided (pass COMMIT_FILE, --from-file, --stdin, or --message)"
         ));
     };
 
-    Ok(MessageData { text, source })
+    let (text, mut encoding) =
+        decode(&bytes, args.encoding.as_deref()).map_err(|err| match &source {
+            MessageSource::File(path) => anyhow!("commit message file {} {err}", path.display()),
+            _ => anyhow!("commit message from {} {err}", source.label()),
+        })?;
+    encoding.bom &= !args.strip_bom;
+    Ok(MessageData {
+        text,
+        source,
+        encoding,
+    })
+}
+
+fn read_changed_files(list: &std::path::Path, message_on_stdin: bool) -> Result<Vec<PathBuf>> {
+    let content = if list.as_os_str() == "-" {
+        if message_on_stdin {
+            return Err(anyhow!(
+                "`--changed-files-from -` cannot be combined with `--stdin`"
+            ));
+        }
+        let mut buf = String::new();
+        io::stdin()
+            .read_to_string(&mut buf)
+            .context("failed to read changed files from stdin")?;
+        buf
+    } else {
+        fs::read_to_string(list)
+            .with_context(|| format!("failed to read changed files from {}", list.display()))?
+    };
+
+    Ok(content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(PathBuf::from)
+        .collect())
 }
 
 fn parse_exclude_arg(raw: &str) -> Result<(String, Option<String>)> {
@@ -466,6 +1112,8 @@ fn parse_cleanup_arg(raw: &str) -> Result<(String, String)> {
 struct MessageData {
     text: String,
     source: MessageSource,
+    /// Encoding the message was read in; `--write` stores the rewrite the same way.
+    encoding: TextEncoding,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -475,6 +1123,63 @@ enum MessageSource {
     Literal,
 }
 
+impl MessageSource {
+    fn label(&self) -> String {
+        match self {
+            MessageSource::File(path) => path.display().to_string(),
+            MessageSource::Stdin => "stdin".to_string(),
+            MessageSource::Literal => "message".to_string(),
+        }
+    }
+}
+
+/// Reprint `message` with line numbers and a caret line under each located finding, in the style
+/// of a compiler diagnostic. Findings without a location are listed as notes at the end.
+fn render_explanation(
+    message: &str,
+    markers: &[Marker],
+    violations: &[Violation],
+    warnings: &[Violation],
+) -> String {
+    let lines: Vec<&str> = message.lines().collect();
+    let gutter = lines.len().max(1).to_string().len();
+    let mut out = String::new();
+
+    for (idx, line) in lines.iter().enumerate() {
+        if line.is_empty() {
+            out.push_str(&format!("{:>gutter$} |\n", idx + 1));
+        } else {
+            out.push_str(&format!("{:>gutter$} | {line}\n", idx + 1));
+        }
+        let mut line_markers: Vec<&Marker> =
+            markers.iter().filter(|m| m.span.line == idx).collect();
+        line_markers.sort_by_key(|m| m.span.column);
+        for marker in line_markers {
+            let level = if marker.warning { "warning" } else { "error" };
+            out.push_str(&format!(
+                "{:gutter$} | {}{} {level}: {}\n",
+                "",
+                " ".repeat(marker.span.column),
+                "^".repeat(marker.span.width),
+                marker.message
+            ));
+        }
+    }
+
+    let located = |finding: &Violation, warning: bool| {
+        markers
+            .iter()
+            .any(|m| m.warning == warning && m.message == finding.message)
+    };
+    for violation in violations.iter().filter(|v| !located(v, false)) {
+        out.push_str(&format!("{:gutter$} = error: {violation}\n", ""));
+    }
+    for warning in warnings.iter().filter(|v| !located(v, true)) {
+        out.push_str(&format!("{:gutter$} = warning: {warning}\n", ""));
+    }
+    out
+}
+
 fn format_error(err: &anyhow::Error) -> String {
     let mut msg = err.to_string();
     for cause in err.chain().skip(1) {
@@ -486,42 +1191,323 @@ fn format_error(err: &anyhow::Error) -> String {
 fn hook_label(kind: crate::hooks::HookKind) -> &'static str {
     match kind {
         crate::hooks::HookKind::CommitMsg => "commit-msg",
+        crate::hooks::HookKind::PrePush => "pre-push",
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Level {
+    Error,
+    Warning,
+    Info,
+}
+
 struct Reporter {
     color: bool,
+    format: OutputFormat,
+    quiet: bool,
+    /// Print `verbose:` lines tracing what gitfluff did to the message.
+    verbose: bool,
+    /// Print a `timing:` line after a batch run.
+    timing: bool,
+    /// Label from `--input-name`, shown after the level on every line.
+    context: Option<String>,
+    /// Stdout carries the rewritten message, so every report goes to stderr instead.
+    stdout_is_content: bool,
+    /// Findings held back by `--format grouped` until [`Reporter::flush_groups`].
+    grouped: Vec<GroupedFinding>,
     stderr: io::Stderr,
 }
 
+/// One entry under a `--format grouped` heading; `id` is `None` for `... and N more`.
+struct GroupedFinding {
+    level: Level,
+    id: Option<&'static str>,
+    message: String,
+}
+
 impl Reporter {
     fn new(mode: ColorMode) -> Self {
-        let is_tty = io::stderr().is_terminal();
         let color = match mode {
-            ColorMode::Auto => is_tty,
+            ColorMode::Auto => auto_color(io::stderr().is_terminal()),
             ColorMode::Always => true,
             ColorMode::Never => false,
         };
 
         Self {
             color,
+            format: OutputFormat::Human,
+            quiet: false,
+            verbose: false,
+            timing: false,
+            context: None,
+            stdout_is_content: false,
+            grouped: Vec::new(),
             stderr: io::stderr(),
         }
     }
 
+    fn with_format(mut self, format: OutputFormat) -> Self {
+        self.format = format;
+        self
+    }
+
     fn error(&mut self, msg: impl AsRef<str>) -> io::Result<()> {
-        self.write_line("error", msg.as_ref(), Some(Ansi::Red))
+        match self.format {
+            OutputFormat::Human | OutputFormat::Grouped => {
+                self.write_line("error", msg.as_ref(), Some(Ansi::Red))
+            }
+            OutputFormat::Github => self.workflow_command("error", msg.as_ref()),
+            OutputFormat::Json | OutputFormat::Sarif | OutputFormat::Porcelain => Ok(()),
+        }
     }
 
     fn info(&mut self, msg: impl AsRef<str>) -> io::Result<()> {
+        if self.quiet || self.format.is_document() {
+            return Ok(());
+        }
         self.write_line("info", msg.as_ref(), Some(Ansi::Cyan))
     }
 
+    fn detail(&mut self, msg: impl AsRef<str>) -> io::Result<()> {
+        if !self.verbose {
+            return Ok(());
+        }
+        self.write_line("verbose", msg.as_ref(), None)
+    }
+
+    /// `--timing` line for a batch: message count, total and per-message time, and how much of
+    /// it went to git when commits were read from a range.
+    fn timing_summary(
+        &mut self,
+        messages: usize,
+        total: Duration,
+        git: Option<Duration>,
+    ) -> io::Result<()> {
+        if !self.timing {
+            return Ok(());
+        }
+        let ms = |duration: Duration| format!("{:.2}ms", duration.as_secs_f64() * 1000.0);
+        let per_message = total / u32::try_from(messages.max(1)).unwrap_or(u32::MAX);
+        let breakdown = match git {
+            Some(git) => format!("; git {}, lint {}", ms(git), ms(total.saturating_sub(git))),
+            None => String::new(),
+        };
+        self.write_line(
+            "timing",
+            &format!(
+                "linted {messages} message(s) in {} ({} per message{breakdown})",
+                ms(total),
+                ms(per_message)
+            ),
+            None,
+        )
+    }
+
+    fn suggest(&mut self, msg: impl AsRef<str>) -> io::Result<()> {
+        if self.format.is_document() {
+            return Ok(());
+        }
+        self.write_line("suggest", msg.as_ref(), Some(Ansi::Green))
+    }
+
     fn warn(&mut self, msg: impl AsRef<str>) -> io::Result<()> {
-        self.write_line("warn", msg.as_ref(), Some(Ansi::Yellow))
+        match self.format {
+            OutputFormat::Human | OutputFormat::Grouped => {
+                self.write_line("warn", msg.as_ref(), Some(Ansi::Yellow))
+            }
+            OutputFormat::Github => self.workflow_command("warning", msg.as_ref()),
+            OutputFormat::Json | OutputFormat::Sarif | OutputFormat::Porcelain => Ok(()),
+        }
+    }
+
+    /// Report a failed check as `error[rule-id]: message`.
+    fn violation(&mut self, violation: &Violation) -> io::Result<()> {
+        let message = with_origin(violation);
+        match self.format {
+            OutputFormat::Human => {
+                self.write_line(
+                    &format!("error[{}]", violation.id),
+                    &message,
+                    Some(Ansi::Red),
+                )?;
+                if violation.fixable {
+                    self.write_line("hint", "run with --write to fix", Some(Ansi::Cyan))?;
+                }
+                Ok(())
+            }
+            OutputFormat::Grouped => self.group(Level::Error, Some(violation.id), message),
+            OutputFormat::Porcelain => self.porcelain("error", violation.id, &message),
+            _ => self.error(&message),
+        }
+    }
+
+    /// Report a warning-level finding as `warn[rule-id]: message`.
+    fn warning(&mut self, warning: &Violation) -> io::Result<()> {
+        let message = with_origin(warning);
+        match self.format {
+            OutputFormat::Human => self.write_line(
+                &format!("warn[{}]", warning.id),
+                &message,
+                Some(Ansi::Yellow),
+            ),
+            OutputFormat::Grouped => self.group(Level::Warning, Some(warning.id), message),
+            OutputFormat::Porcelain => self.porcelain("warning", warning.id, &message),
+            _ => self.warn(&message),
+        }
+    }
+
+    /// Note how many findings `--max-violations` left out of a list of `total`.
+    fn truncated(&mut self, total: usize, cap: Option<usize>, level: Level) -> io::Result<()> {
+        let Some(cap) = cap.filter(|&cap| total > cap) else {
+            return Ok(());
+        };
+        let msg = format!("... and {} more", total - cap);
+        if self.format == OutputFormat::Grouped && level != Level::Info {
+            return self.group(level, None, msg);
+        }
+        match level {
+            Level::Error => self.error(msg),
+            Level::Warning => self.warn(msg),
+            Level::Info => self.info(msg),
+        }
+    }
+
+    fn group(&mut self, level: Level, id: Option<&'static str>, message: String) -> io::Result<()> {
+        self.grouped.push(GroupedFinding { level, id, message });
+        Ok(())
+    }
+
+    /// Print the findings held back by `--format grouped`: an `errors:` and a `warnings:` heading,
+    /// each followed by `  • rule-id  message` lines with the messages aligned.
+    fn flush_groups(&mut self) -> io::Result<()> {
+        let findings = std::mem::take(&mut self.grouped);
+        let width = findings
+            .iter()
+            .filter_map(|finding| finding.id)
+            .map(str::len)
+            .max()
+            .unwrap_or(0);
+        let mut stderr = self.stderr.lock();
+        for (level, heading, color) in [
+            (Level::Error, "errors", Ansi::Red),
+            (Level::Warning, "warnings", Ansi::Yellow),
+        ] {
+            let mut group = findings
+                .iter()
+                .filter(|finding| finding.level == level)
+                .peekable();
+            if group.peek().is_none() {
+                continue;
+            }
+            let heading = match &self.context {
+                Some(context) => format!("{heading}[{context}]"),
+                None => heading.to_string(),
+            };
+            if self.color {
+                writeln!(stderr, "{}{heading}{}:", color.code(), Ansi::Reset.code())?;
+            } else {
+                writeln!(stderr, "{heading}:")?;
+            }
+            for finding in group {
+                let Some(id) = finding.id else {
+                    writeln!(stderr, "  {}", finding.message)?;
+                    continue;
+                };
+                let padding = " ".repeat(width - id.len());
+                let mut lines = finding.message.split('\n');
+                let first = lines.next().unwrap_or_default();
+                if self.color {
+                    writeln!(
+                        stderr,
+                        "  • {}{id}{}{padding}  {first}",
+                        Ansi::Dim.code(),
+                        Ansi::Reset.code()
+                    )?;
+                } else {
+                    writeln!(stderr, "  • {id}{padding}  {first}")?;
+                }
+                for line in lines {
+                    writeln!(stderr, "    {}  {line}", " ".repeat(width))?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// One `--porcelain` record; tabs and newlines in the message become spaces.
+    fn porcelain(&mut self, severity: &str, id: &str, message: &str) -> io::Result<()> {
+        let message = message.replace(['\t', '\n'], " ");
+        self.report(&format!("{severity}\t{id}\t{message}"))
+    }
+
+    /// Print a machine-readable report line on stdout, or on stderr while stdout carries the
+    /// rewritten message.
+    fn report(&mut self, line: &str) -> io::Result<()> {
+        if self.stdout_is_content {
+            writeln!(self.stderr.lock(), "{line}")
+        } else {
+            writeln!(io::stdout().lock(), "{line}")
+        }
+    }
+
+    fn workflow_command(&mut self, command: &str, msg: &str) -> io::Result<()> {
+        self.report(&workflow_command(command, msg))
+    }
+
+    /// Closing `gitfluff: 3 errors, 1 warning` line for human output with findings.
+    fn summary(&mut self, errors: usize, warnings: usize) -> io::Result<()> {
+        if self.quiet || !self.format.is_human() || errors + warnings == 0 {
+            return Ok(());
+        }
+        let plural = |count: usize, noun: &str| {
+            if count == 1 {
+                format!("{count} {noun}")
+            } else {
+                format!("{count} {noun}s")
+            }
+        };
+        let mut stderr = self.stderr.lock();
+        writeln!(
+            stderr,
+            "gitfluff: {}, {}",
+            plural(errors, "error"),
+            plural(warnings, "warning")
+        )
+    }
+
+    fn raw(&mut self, text: &str) -> io::Result<()> {
+        let mut stderr = self.stderr.lock();
+        stderr.write_all(text.as_bytes())
+    }
+
+    fn diff(&mut self, diff: &str) -> io::Result<()> {
+        let mut stderr = self.stderr.lock();
+        for line in diff.lines() {
+            let color = if !self.color || line.starts_with("---") || line.starts_with("+++") {
+                None
+            } else if line.starts_with('-') {
+                Some(Ansi::Red)
+            } else if line.starts_with('+') {
+                Some(Ansi::Green)
+            } else if line.starts_with('@') {
+                Some(Ansi::Cyan)
+            } else {
+                None
+            };
+            match color {
+                Some(color) => writeln!(stderr, "{}{}{}", color.code(), line, Ansi::Reset.code())?,
+                None => writeln!(stderr, "{line}")?,
+            }
+        }
+        Ok(())
     }
 
     fn write_line(&mut self, level: &str, msg: &str, color: Option<Ansi>) -> io::Result<()> {
+        let level = match &self.context {
+            Some(context) => format!("{level}[{context}]"),
+            None => level.to_string(),
+        };
         let mut stderr = self.stderr.lock();
         for line in msg.split('\n') {
             if self.color {
@@ -545,11 +1531,31 @@ impl Reporter {
     }
 }
 
+/// The finding's message, followed by ` (from <file>:<line>)` when a config entry produced it.
+fn with_origin(finding: &Violation) -> String {
+    match &finding.origin {
+        Some(origin) => format!("{} (from {origin})", finding.message),
+        None => finding.message.clone(),
+    }
+}
+
+/// Format a GitHub Actions workflow command (`::error title=gitfluff::...`). Commit messages
+/// have no file/line of their own, so the annotation is attached to the run.
+fn workflow_command(command: &str, msg: &str) -> String {
+    let escaped = msg
+        .replace('%', "%25")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A");
+    format!("::{command} title=gitfluff::{escaped}")
+}
+
 #[derive(Clone, Copy)]
 enum Ansi {
     Red,
+    Green,
     Yellow,
     Cyan,
+    Dim,
     Reset,
 }
 
@@ -557,33 +1563,83 @@ impl Ansi {
     fn code(self) -> &'static str {
         match self {
             Ansi::Red => "\x1b[31m",
+            Ansi::Green => "\x1b[32m",
             Ansi::Yellow => "\x1b[33m",
             Ansi::Cyan => "\x1b[36m",
+            Ansi::Dim => "\x1b[2m",
             Ansi::Reset => "\x1b[0m",
         }
     }
 }
 
-fn is_merge_commit_in_progress(start_dir: &std::path::Path) -> bool {
+/// Emergency escape hatch for hooks: `GITFLUFF_SKIP=1` (or `true`) bypasses linting.
+fn skip_requested() -> bool {
+    env_flag("GITFLUFF_SKIP")
+}
+
+/// `--color auto`: any `NO_COLOR` disables color, a non-`0` `FORCE_COLOR` / `CLICOLOR_FORCE`
+/// enables it, and otherwise color follows whether stderr is a terminal.
+fn auto_color(is_tty: bool) -> bool {
+    if std::env::var_os("NO_COLOR").is_some() {
+        return false;
+    }
+    let forced = |name: &str| std::env::var_os(name).is_some_and(|value| value != "0");
+    forced("FORCE_COLOR") || forced("CLICOLOR_FORCE") || is_tty
+}
+
+/// Whether an environment variable is set to `1` or `true`.
+fn env_flag(name: &str) -> bool {
+    std::env::var(name).is_ok_and(|value| {
+        let value = value.trim();
+        value == "1" || value.eq_ignore_ascii_case("true")
+    })
+}
+
+/// Non-empty value of an environment variable used as a low-priority fallback for a setting.
+fn env_setting(name: &str) -> Option<String> {
+    std::env::var(name)
+        .ok()
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+/// Number of heads listed in `MERGE_HEAD` when a merge commit is in progress.
+fn pending_merge_heads(start_dir: &std::path::Path) -> Option<usize> {
+    read_merge_heads(&find_git_dir(start_dir)?)
+}
+
+/// The branch `HEAD` points at, or `None` when detached or outside a repository.
+fn current_branch(start_dir: &std::path::Path) -> Option<String> {
+    let head = fs::read_to_string(find_git_dir(start_dir)?.join("HEAD")).ok()?;
+    head.trim()
+        .strip_prefix("ref: refs/heads/")
+        .map(str::to_string)
+}
+
+/// The `.git` directory for `start_dir`, following `gitdir:` files used by worktrees.
+fn find_git_dir(start_dir: &std::path::Path) -> Option<std::path::PathBuf> {
     let mut current = start_dir;
     loop {
         let git_dir = current.join(".git");
         if git_dir.is_dir() {
-            return git_dir.join("MERGE_HEAD").exists();
+            return Some(git_dir);
         }
         if git_dir.is_file() {
-            if let Ok(resolved) = resolve_gitdir_file(&git_dir) {
-                return resolved.join("MERGE_HEAD").exists();
-            }
-            return false;
-        }
-        match current.parent() {
-            Some(parent) => current = parent,
-            None => return false,
+            return resolve_gitdir_file(&git_dir).ok();
         }
+        current = current.parent()?;
     }
 }
 
+fn read_merge_heads(git_dir: &std::path::Path) -> Option<usize> {
+    let content = fs::read_to_string(git_dir.join("MERGE_HEAD")).ok()?;
+    let heads = content
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .count();
+    Some(heads.max(1))
+}
+
 fn resolve_gitdir_file(git_file: &std::path::Path) -> Result<std::path::PathBuf> {
     let content = fs::read_to_string(git_file)
         .with_context(|| format!("failed to read gitdir file {}", git_file.display()))?;
@@ -6,6 +6,12 @@ pub struct Preset {
     pub description: &'static str,
     pub body_policy: BodyPolicy,
     pub enforce_spec: bool,
+    /// Type list replacing the default `type-enum`, unless the config sets `allowed_types`.
+    pub allowed_types: Option<&'static [&'static str]>,
+    /// Subject length cap, unless the config or CLI sets `subject_max_length`.
+    pub subject_max_length: Option<usize>,
+    /// Body line length cap, unless the config sets `body_max_line_length`.
+    pub body_max_line_length: Option<usize>,
 }
 
 // Align with commitlint's default `headerPattern` (via `conventional-changelog-conventionalcommits`):
@@ -20,7 +26,9 @@ pub fn resolve_preset(name: &str) -> Option<Preset> {
         "conventional-body" | "conventional_detailed" | "conventional-with-body" => {
             Some(conventional_with_body())
         }
+        "angular" => Some(angular()),
         "simple" | "simple-single-line" => Some(simple_single_line()),
+        "fifty-seventy-two" | "50/72" => Some(fifty_seventy_two()),
         _ => None,
     }
 }
@@ -31,6 +39,9 @@ fn conventional() -> Preset {
         description: "Conventional Commits title line (AI signatures are cleaned automatically)",
         body_policy: BodyPolicy::Any,
         enforce_spec: true,
+        allowed_types: None,
+        subject_max_length: None,
+        body_max_line_length: None,
     }
 }
 
@@ -40,6 +51,38 @@ fn conventional_with_body() -> Preset {
         description: "Conventional Commits title line with a required body section",
         body_policy: BodyPolicy::RequireBody,
         enforce_spec: true,
+        allowed_types: None,
+        subject_max_length: None,
+        body_max_line_length: None,
+    }
+}
+
+/// Angular's commit convention, matching `@commitlint/config-angular` (no `chore`).
+fn angular() -> Preset {
+    Preset {
+        message_pattern: CONVENTIONAL_PATTERN,
+        description: "Angular commit message title line",
+        body_policy: BodyPolicy::Any,
+        enforce_spec: true,
+        allowed_types: Some(&[
+            "build", "ci", "docs", "feat", "fix", "perf", "refactor", "revert", "style", "test",
+        ]),
+        subject_max_length: None,
+        body_max_line_length: None,
+    }
+}
+
+/// The classic git style: a summary of at most 50 characters and a body wrapped at 72, with no
+/// opinion on type prefixes.
+fn fifty_seventy_two() -> Preset {
+    Preset {
+        message_pattern: "^\\S.*$",
+        description: "Summary line of at most 50 characters",
+        body_policy: BodyPolicy::Any,
+        enforce_spec: false,
+        allowed_types: None,
+        subject_max_length: Some(50),
+        body_max_line_length: Some(72),
     }
 }
 
@@ -50,5 +93,8 @@ fn simple_single_line() -> Preset {
         description: "Single-line summary starting with a letter",
         body_policy: BodyPolicy::SingleLine,
         enforce_spec: false,
+        allowed_types: None,
+        subject_max_length: None,
+        body_max_line_length: None,
     }
 }
@@ -0,0 +1,577 @@
+//! Turning a preset, a config file, and command-line overrides into [`LintOptions`].
+
+use std::path::Path;
+
+use anyhow::{Context, Result, anyhow, bail};
+
+use crate::config::{ConfigLocation, Convention, FileConfig};
+use crate::lint::{
+    BodyPolicy, LengthUnit, LineEnding, LintOptions, RuleSource, SubjectCaseScope, Violation,
+    build_cleanup_rule, build_exclude_rule, build_message_pattern, build_message_patterns,
+    build_title_prefix_rule, build_title_suffix_rule, lint_message, secret_exclude_rules,
+    validate_autofix_steps,
+};
+use crate::presets::resolve_preset;
+
+const AI_EXCLUDE_RULES: &[(&str, &str)] = &[
+    (
+        "(?mi)^Co-Authored-By:.*(?:Claude|Anthropic|ChatGPT|GPT|OpenAI).*$",
+        "Remove AI co-author attribution lines",
+    ),
+    (
+        "🤖 Generated with",
+        "Remove AI generation notices from commit messages",
+    ),
+];
+
+const WIP_EXCLUDE_RULE: (&str, &str) = ("(?i)^(?:wip\\b|\\[wip\\])", "WIP commits are not allowed");
+
+const AI_CLEANUP_RULES: &[(&str, &str, &str)] = &[
+    (
+        "(?ims)\\n?\\s*(?:🤖\\s*)?Generated with.*?(?:Co-Authored-By:.*(?:Claude|Anthropic).*(?:\\n\\s*<[^>\\n]+>)?)+\\s*",
+        "\n",
+        "Remove Claude Code attribution block",
+    ),
+    (
+        "(?m)^.*🤖 Generated with.*\n?",
+        "",
+        "Remove AI generation banner",
+    ),
+    (
+        "(?mi)^Generated with Claude.*\n?",
+        "",
+        "Remove plain Claude generation banner",
+    ),
+    (
+        "(?mi)^Co-Authored-By:.*(?:Claude|Anthropic).*\n?",
+        "",
+        "Drop Co-Authored-By lines referencing AI assistants",
+    ),
+    ("(?mi)^-\\s*Claude.*\n?", "", "Remove Claude bullet entries"),
+    (
+        "(?s)\\A\\s*\n+",
+        "",
+        "Trim leading blank lines introduced by cleanup",
+    ),
+    (
+        "(?s)\n\\s*\n\\z",
+        "\n",
+        "Trim trailing blank lines introduced by cleanup",
+    ),
+    ("\n{3,}", "\n\n", "Collapse excessive blank lines"),
+];
+
+const DEFAULT_TITLE_PREFIX_SEPARATOR: &str = " * ";
+const DEFAULT_TITLE_SUFFIX_SEPARATOR: &str = " ";
+
+/// Settings given on the command line. Each one that is set wins over the config file, which in
+/// turn wins over the preset; `false`, `None`, and empty lists defer to the config.
+#[derive(Debug, Clone, Default)]
+pub struct CliOverrides {
+    pub preset: Option<String>,
+    /// Preset used when neither `preset` nor the config names one (the CLI reads it from
+    /// `GITFLUFF_PRESET`).
+    pub fallback_preset: Option<String>,
+    pub msg_pattern: Option<String>,
+    pub msg_pattern_description: Option<String>,
+    /// `(pattern, message)` exclude rules, added after the config's.
+    pub exclude: Vec<(String, Option<String>)>,
+    /// `(find, replace, description)` cleanup rules, added after the config's.
+    pub cleanup: Vec<(String, String, Option<String>)>,
+    pub no_emojis: bool,
+    pub ascii_only: bool,
+    /// `(pattern, separator)` for the required title prefix.
+    pub title_prefix: Option<(String, String)>,
+    /// `(pattern, separator)` for the required title suffix.
+    pub title_suffix: Option<(String, String)>,
+    pub subject_lowercase_first: bool,
+    pub breaking_requires_footer: bool,
+    pub single_line: bool,
+    pub require_body: bool,
+    /// Rewrite the message (`--write`, `--dry-run`, or `--fix-only`).
+    pub write: bool,
+    pub autofix_only: Vec<String>,
+    pub body_wrap: Option<usize>,
+    pub body_max_lines: Option<usize>,
+    pub subject_max_length: Option<usize>,
+    pub subject_case_scope: Option<SubjectCaseScope>,
+    pub length_unit: Option<LengthUnit>,
+    pub line_ending: Option<LineEnding>,
+}
+
+/// Assemble the options `gitfluff lint` would use for `cli` and `config`, including the built-in
+/// AI-attribution rules. `cwd` is only used to shorten config paths in rule sources.
+pub fn resolve_options(
+    cli: &CliOverrides,
+    config: Option<&FileConfig>,
+    cwd: &Path,
+) -> Result<LintOptions> {
+    let preset_name = cli
+        .preset
+        .clone()
+        .or_else(|| config.and_then(|cfg| cfg.preset.clone()))
+        .or_else(|| cli.fallback_preset.clone())
+        .unwrap_or_else(|| "conventional".to_string());
+
+    let preset =
+        resolve_preset(&preset_name).ok_or_else(|| anyhow!("unknown preset `{}`", preset_name))?;
+
+    let mut enforce_spec = preset.enforce_spec;
+    let mut message_pattern = Some(build_message_pattern(
+        preset.message_pattern,
+        Some(preset.description.to_string()),
+        Vec::new(),
+    )?);
+
+    if let Some(cfg) = config
+        && let Some(rule) = &cfg.rules.message
+    {
+        let patterns: Vec<&str> = rule
+            .pattern
+            .iter()
+            .chain(&rule.patterns)
+            .map(String::as_str)
+            .collect();
+        message_pattern = Some(
+            build_message_patterns(
+                &patterns,
+                rule.description.clone(),
+                rule.require_groups.clone(),
+            )
+            .context("invalid [rules.message]")?,
+        );
+        enforce_spec = false;
+    }
+
+    if let Some(pattern) = &cli.msg_pattern {
+        let desc = cli
+            .msg_pattern_description
+            .clone()
+            .or_else(|| Some(format!("Commit message must match pattern `{pattern}`")));
+        message_pattern = Some(build_message_pattern(pattern, desc, Vec::new())?);
+        enforce_spec = false;
+    } else if cli.msg_pattern_description.is_some()
+        && let Some(mp) = message_pattern.as_mut()
+    {
+        mp.description = cli.msg_pattern_description.clone();
+    }
+
+    let mut options = LintOptions {
+        message_pattern,
+        body_policy: preset.body_policy,
+        enforce_conventional_spec: enforce_spec,
+        allowed_types: config
+            .and_then(|cfg| cfg.rules.allowed_types.clone())
+            .or_else(|| {
+                preset
+                    .allowed_types
+                    .map(|types| types.iter().map(|ty| ty.to_string()).collect())
+            }),
+        ..Default::default()
+    };
+
+    let mut body_policy = preset.body_policy;
+    let mut forbid_emojis = false;
+    let mut forbid_non_ascii = false;
+    let mut subject_lowercase_first = false;
+    let mut title_prefix_pattern: Option<String> = None;
+    let mut title_prefix_separator = DEFAULT_TITLE_PREFIX_SEPARATOR.to_string();
+    let mut title_suffix_pattern: Option<String> = None;
+    let mut title_suffix_separator = DEFAULT_TITLE_SUFFIX_SEPARATOR.to_string();
+
+    if let Some(cfg) = config {
+        let single_line_flag = cfg.rules.single_line.unwrap_or(false);
+        let require_body_flag = cfg.rules.require_body.unwrap_or(false);
+        forbid_emojis = cfg.rules.no_emojis.unwrap_or(false);
+        forbid_non_ascii = cfg.rules.ascii_only.unwrap_or(false);
+        subject_lowercase_first = cfg.rules.subject_lowercase_first.unwrap_or(false);
+
+        if let Some(pattern) = &cfg.rules.title_prefix {
+            title_prefix_pattern = Some(pattern.clone());
+        }
+        if let Some(separator) = &cfg.rules.title_prefix_separator {
+            title_prefix_separator = separator.clone();
+        }
+        if let Some(pattern) = &cfg.rules.title_suffix {
+            title_suffix_pattern = Some(pattern.clone());
+        }
+        if let Some(separator) = &cfg.rules.title_suffix_separator {
+            title_suffix_separator = separator.clone();
+        }
+
+        if single_line_flag && require_body_flag {
+            return Err(anyhow!(
+                "configuration cannot enable both `single_line` and `require_body` rules"
+            ));
+        }
+
+        if single_line_flag {
+            body_policy = BodyPolicy::SingleLine;
+        } else if require_body_flag {
+            body_policy = BodyPolicy::RequireBody;
+        } else {
+            if matches!(cfg.rules.single_line, Some(false))
+                && matches!(body_policy, BodyPolicy::SingleLine)
+            {
+                body_policy = BodyPolicy::Any;
+            }
+            if matches!(cfg.rules.require_body, Some(false))
+                && matches!(body_policy, BodyPolicy::RequireBody)
+            {
+                body_policy = BodyPolicy::Any;
+            }
+        }
+
+        for exclude in &cfg.rules.excludes {
+            let mut rule = build_exclude_rule(&exclude.pattern, exclude.message.clone())?;
+            rule.source = config_rule_source(exclude.defined_at.as_ref(), cwd);
+            options.exclude_rules.push(rule);
+        }
+
+        for cleanup in &cfg.rules.cleanup {
+            let mut rule =
+                build_cleanup_rule(&cleanup.find, &cleanup.replace, cleanup.description.clone())?;
+            rule.source = config_rule_source(cleanup.defined_at.as_ref(), cwd);
+            options.cleanup_rules.push(rule);
+        }
+    }
+
+    for (pattern, message) in &cli.exclude {
+        options
+            .exclude_rules
+            .push(build_exclude_rule(pattern, message.clone())?);
+    }
+
+    for (find, replace, description) in &cli.cleanup {
+        options
+            .cleanup_rules
+            .push(build_cleanup_rule(find, replace, description.clone())?);
+    }
+
+    if cli.single_line {
+        body_policy = BodyPolicy::SingleLine;
+    } else if cli.require_body {
+        body_policy = BodyPolicy::RequireBody;
+    }
+
+    if cli.no_emojis {
+        forbid_emojis = true;
+    }
+    if cli.ascii_only {
+        forbid_non_ascii = true;
+    }
+    if cli.subject_lowercase_first {
+        subject_lowercase_first = true;
+    }
+    if let Some((pattern, separator)) = &cli.title_prefix {
+        title_prefix_pattern = Some(pattern.clone());
+        title_prefix_separator = separator.clone();
+    }
+    if let Some((pattern, separator)) = &cli.title_suffix {
+        title_suffix_pattern = Some(pattern.clone());
+        title_suffix_separator = separator.clone();
+    }
+
+    options.autofix = cli.write || config.and_then(|cfg| cfg.write).unwrap_or(false);
+    options.autofix_only = if cli.autofix_only.is_empty() {
+        config.and_then(|cfg| cfg.rules.autofix_only.clone())
+    } else {
+        Some(cli.autofix_only.clone())
+    };
+    options.body_wrap = cli
+        .body_wrap
+        .or_else(|| config.and_then(|cfg| cfg.rules.body_wrap));
+    if let Some(steps) = &options.autofix_only {
+        validate_autofix_steps(steps)?;
+    }
+    options.body_max_lines = cli
+        .body_max_lines
+        .or_else(|| config.and_then(|cfg| cfg.rules.body_max_lines));
+    if let Some(cfg) = config {
+        options.type_aliases = cfg
+            .rules
+            .type_aliases
+            .iter()
+            .map(|(alias, canonical)| (alias.to_lowercase(), canonical.clone()))
+            .collect();
+        options.require_body_for = cfg.rules.require_body_for.clone();
+        options.forbid_body_for = cfg.rules.forbid_body_for.clone();
+        options.preserve_coauthors = cfg.rules.preserve_coauthors.clone();
+        options.forbid_placeholders = cfg.rules.forbid_placeholders.clone();
+        options.allow_tabs_in_header = cfg.rules.allow_tabs_in_header.unwrap_or(false);
+        options.allowed_scopes = cfg.rules.allowed_scopes.clone();
+        options.allowed_footer_tokens = cfg.rules.allowed_footer_tokens.clone();
+        options.max_footers = cfg.rules.max_footers;
+        options.max_message_bytes = cfg.rules.max_message_bytes;
+        options.strict_blank_lines = cfg.rules.strict_blank_lines.unwrap_or(false);
+        options.breaking_change_last = cfg.rules.breaking_change_last.unwrap_or(false);
+        options.no_issue_ref_in_subject = cfg.rules.no_issue_ref_in_subject.unwrap_or(false);
+        options.single_sentence_subject = cfg.rules.single_sentence_subject.unwrap_or(false);
+        options.move_trailing_ref = cfg.rules.move_trailing_ref.unwrap_or(false);
+        options.normalize_unicode = cfg.rules.normalize_unicode.unwrap_or(false);
+        options.forbid_header_emoji = cfg.rules.no_emoji_header.unwrap_or(false);
+        options.header_separator = cfg.rules.header_separator.unwrap_or_default();
+        options.subject_case = cfg.rules.subject_case.unwrap_or_default();
+        options.scope_case = cfg.rules.scope_case.unwrap_or_default();
+        options.cleanup_mode = cfg.rules.cleanup_mode.unwrap_or_default();
+        options.body_bullet_style = cfg.rules.body_bullet_style;
+        options.body_capitalized = cfg.rules.body_capitalized.unwrap_or(false);
+        options.no_redundant_body_first_line =
+            cfg.rules.no_redundant_body_first_line.unwrap_or(false);
+        options.revert_requires_reason = cfg.rules.revert_requires_reason.unwrap_or(false);
+        options.validate_squash_bullets = cfg.rules.validate_squash_bullets.unwrap_or(false);
+        options.header_max_length = cfg.rules.header_max_length;
+    }
+    let convention_lengths = match config.and_then(|cfg| cfg.rules.convention) {
+        Some(Convention::FiftySeventyTwo) => (Some(50), Some(72)),
+        None => (None, None),
+    };
+    options.body_max_line_length = config
+        .and_then(|cfg| cfg.rules.body_max_line_length)
+        .or(convention_lengths.1)
+        .or(preset.body_max_line_length);
+    options.breaking_requires_footer = cli.breaking_requires_footer
+        || config
+            .and_then(|cfg| cfg.rules.breaking_requires_footer)
+            .unwrap_or(false);
+    options.subject_max_length = cli
+        .subject_max_length
+        .or_else(|| config.and_then(|cfg| cfg.rules.subject_max_length))
+        .or(convention_lengths.0)
+        .or(preset.subject_max_length);
+
+    options.body_policy = body_policy;
+    options.forbid_emojis = forbid_emojis;
+    options.forbid_non_ascii = forbid_non_ascii;
+    options.subject_lowercase_first = subject_lowercase_first;
+    if let Some(scope) = cli
+        .subject_case_scope
+        .or_else(|| config.and_then(|cfg| cfg.rules.subject_case_scope))
+    {
+        options.subject_case_scope = scope;
+    }
+    if let Some(unit) = cli
+        .length_unit
+        .or_else(|| config.and_then(|cfg| cfg.rules.length_unit))
+    {
+        options.length_unit = unit;
+    }
+    if let Some(line_ending) = cli
+        .line_ending
+        .or_else(|| config.and_then(|cfg| cfg.rules.line_ending))
+    {
+        options.line_ending = line_ending;
+    }
+
+    if let Some(pattern) = title_prefix_pattern.as_ref() {
+        options.title_prefix = Some(build_title_prefix_rule(pattern, &title_prefix_separator)?);
+    }
+
+    if let Some(pattern) = title_suffix_pattern.as_ref() {
+        options.title_suffix = Some(build_title_suffix_rule(pattern, &title_suffix_separator)?);
+    }
+
+    for (pattern, message) in AI_EXCLUDE_RULES {
+        let mut rule = build_exclude_rule(pattern, Some((*message).to_string()))?;
+        rule.id = "ai-attribution";
+        rule.source = RuleSource::BuiltinAi;
+        options.exclude_rules.push(rule);
+    }
+
+    if config
+        .and_then(|cfg| cfg.rules.detect_secrets)
+        .unwrap_or(false)
+    {
+        options.exclude_rules.extend(secret_exclude_rules());
+    }
+
+    if config.and_then(|cfg| cfg.rules.no_wip).unwrap_or(false) {
+        let (pattern, message) = WIP_EXCLUDE_RULE;
+        let mut rule = build_exclude_rule(pattern, Some(message.to_string()))?;
+        rule.id = "wip";
+        rule.source = RuleSource::Builtin;
+        options.exclude_rules.push(rule);
+    }
+
+    for (find, replace, desc) in AI_CLEANUP_RULES {
+        let mut rule = build_cleanup_rule(find, replace, Some((*desc).to_string()))?;
+        rule.source = RuleSource::BuiltinAi;
+        options.cleanup_rules.push(rule);
+    }
+
+    Ok(options)
+}
+
+/// Source for a rule defined in a config file, with the path shown relative to `cwd` when possible.
+fn config_rule_source(defined_at: Option<&ConfigLocation>, cwd: &Path) -> RuleSource {
+    match defined_at {
+        Some(location) => RuleSource::Config {
+            path: location
+                .path
+                .strip_prefix(cwd)
+                .unwrap_or(&location.path)
+                .to_path_buf(),
+            line: location.line,
+        },
+        None => RuleSource::Cli,
+    }
+}
+
+/// Result of [`validate`]: the findings left after the config's severity overrides and, when
+/// the config enables `write`, after autofix.
+#[derive(Debug, Clone)]
+pub struct Report {
+    pub violations: Vec<Violation>,
+    pub warnings: Vec<Violation>,
+    /// The message after cleanup and autofix; equal to the input when nothing was rewritten.
+    pub cleaned_message: String,
+}
+
+impl Report {
+    pub fn is_valid(&self) -> bool {
+        self.violations.is_empty()
+    }
+}
+
+/// Lint `message` with the options `gitfluff lint` would build from `config` alone.
+///
+/// `rules.rules_file` is read by [`crate::load_config`] while loading a config file, so a config
+/// that still carries one is rejected here rather than silently linting without its rules.
+///
+/// ```
+/// use gitfluff::{FileConfig, validate};
+///
+/// let report = validate("feat: add login.", &FileConfig::default()).unwrap();
+/// assert!(!report.is_valid());
+/// assert_eq!(report.violations[0].id, "subject-full-stop");
+/// ```
+pub fn validate(message: &str, config: &FileConfig) -> Result<Report> {
+    if config.rules.rules_file.is_some() {
+        bail!("`rules_file` is only read by `load_config`; load the config from disk instead");
+    }
+    let options = resolve_options(&CliOverrides::default(), Some(config), Path::new(""))?;
+    let mut outcome = lint_message(message, &options);
+    outcome.apply_severities(&config.rules.severity);
+    let (violations, warnings) = if options.autofix {
+        (outcome.violations_after, outcome.warnings_after)
+    } else {
+        (outcome.violations_before, outcome.warnings_before)
+    };
+    Ok(Report {
+        violations,
+        warnings,
+        cleaned_message: outcome.cleaned_message,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(toml_text: &str) -> FileConfig {
+        toml::from_str(toml_text).unwrap()
+    }
+
+    #[test]
+    fn preset_applies_without_config_or_overrides() {
+        let cli = CliOverrides {
+            preset: Some("simple".into()),
+            ..Default::default()
+        };
+        let options = resolve_options(&cli, None, Path::new("")).unwrap();
+        assert_eq!(options.body_policy, BodyPolicy::SingleLine);
+        assert!(!options.enforce_conventional_spec);
+    }
+
+    #[test]
+    fn config_overrides_preset() {
+        let cfg = config("preset = \"simple\"\n[rules]\nsingle_line = false\n");
+        let options = resolve_options(&CliOverrides::default(), Some(&cfg), Path::new("")).unwrap();
+        assert_eq!(options.body_policy, BodyPolicy::Any);
+
+        let cfg = config("[rules]\nallowed_types = [\"feat\"]\n");
+        let cli = CliOverrides {
+            preset: Some("angular".into()),
+            ..Default::default()
+        };
+        let options = resolve_options(&cli, Some(&cfg), Path::new("")).unwrap();
+        assert_eq!(options.allowed_types, Some(vec!["feat".to_string()]));
+    }
+
+    #[test]
+    fn cli_overrides_config() {
+        let cfg = config(
+            "preset = \"simple\"\nwrite = false\n[rules]\nbody_max_lines = 5\nrequire_body = true\n",
+        );
+        let cli = CliOverrides {
+            preset: Some("conventional".into()),
+            body_max_lines: Some(2),
+            single_line: true,
+            write: true,
+            ..Default::default()
+        };
+        let options = resolve_options(&cli, Some(&cfg), Path::new("")).unwrap();
+        assert!(options.enforce_conventional_spec);
+        assert_eq!(options.body_max_lines, Some(2));
+        assert_eq!(options.body_policy, BodyPolicy::SingleLine);
+        assert!(options.autofix);
+    }
+
+    #[test]
+    fn fallback_preset_ranks_below_config() {
+        let cli = CliOverrides {
+            fallback_preset: Some("simple".into()),
+            ..Default::default()
+        };
+        let options = resolve_options(&cli, None, Path::new("")).unwrap();
+        assert!(!options.enforce_conventional_spec);
+
+        let cfg = config("preset = \"conventional\"\n");
+        let options = resolve_options(&cli, Some(&cfg), Path::new("")).unwrap();
+        assert!(options.enforce_conventional_spec);
+    }
+
+    #[test]
+    fn fifty_seventy_two_convention_fills_unset_length_keys() {
+        let cli = CliOverrides {
+            preset: Some("fifty-seventy-two".into()),
+            ..Default::default()
+        };
+        let options = resolve_options(&cli, None, Path::new("")).unwrap();
+        assert!(!options.enforce_conventional_spec);
+        assert_eq!(options.subject_max_length, Some(50));
+        assert_eq!(options.body_max_line_length, Some(72));
+
+        let cfg = config("[rules]\nconvention = \"50/72\"\nbody_max_line_length = 80\n");
+        let options = resolve_options(&CliOverrides::default(), Some(&cfg), Path::new("")).unwrap();
+        assert!(options.enforce_conventional_spec);
+        assert_eq!(options.subject_max_length, Some(50));
+        assert_eq!(options.body_max_line_length, Some(80));
+    }
+
+    #[test]
+    fn builtin_ai_rules_are_always_added() {
+        let options = resolve_options(&CliOverrides::default(), None, Path::new("")).unwrap();
+        assert!(
+            options
+                .exclude_rules
+                .iter()
+                .any(|rule| rule.id == "ai-attribution")
+        );
+    }
+
+    #[test]
+    fn validate_applies_config_severities() {
+        let cfg = config("[rules.severity]\nsubject-full-stop = \"warn\"\n");
+        let report = validate("feat: add login.", &cfg).unwrap();
+        assert!(report.is_valid());
+        assert_eq!(report.warnings[0].id, "subject-full-stop");
+    }
+
+    #[test]
+    fn validate_rejects_an_unloaded_rules_file() {
+        let cfg = config("[rules]\nrules_file = \"lint/rules.toml\"\n");
+        let err = validate("feat: add login", &cfg).unwrap_err();
+        assert!(err.to_string().contains("rules_file"), "{err}");
+    }
+}